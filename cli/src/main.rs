@@ -1,38 +1,299 @@
-use anyhow::Error;
+use anyhow::{bail, Error};
+use serde::Serialize;
 use structopt::StructOpt;
 
 use std::fs::File;
-use std::io::BufReader;
+use std::io::BufWriter;
+use std::io::Read;
+use std::io::Seek;
 use std::path::PathBuf;
 
-use riff_wave_reader::RiffWaveReader;
+use riff_wave_reader::{
+    DataChunk, Endianness, Error as RiffError, FactChunk, FmtChunk, Format, FourCC, RiffChunk,
+    RiffWaveReader, RiffWaveWriter,
+};
 
 fn main() -> Result<(), Error> {
     let opts = Opts::from_args();
 
     match opts.command {
         Command::Print { input } => {
-            let file = File::open(input)?;
-            let reader = BufReader::new(file);
-
-            let reader = RiffWaveReader::new(reader)?;
+            let reader = RiffWaveReader::open(input)?;
 
             reader.print_info();
         }
         Command::Raw { input } => {
-            let file = File::open(input)?;
-            let reader = BufReader::new(file);
-
-            let mut reader = RiffWaveReader::new(reader)?;
+            let mut reader = RiffWaveReader::open(input)?;
 
             let data = reader.data()?.collect::<Vec<_>>();
             println!("{}", data.len());
         }
+        Command::Json { input } => println!("{}", json(input)?),
+        Command::ExtractChannel {
+            input,
+            channel,
+            output,
+        } => extract_channel(input, channel, output)?,
+        Command::Trim {
+            input,
+            start_secs,
+            end_secs,
+            output,
+        } => trim(input, start_secs, end_secs, output)?,
+        Command::Summary { input } => summary(input)?,
+    }
+
+    Ok(())
+}
+
+fn json(input: PathBuf) -> Result<String, Error> {
+    let reader = RiffWaveReader::open(input)?;
+
+    let info = JsonInfo {
+        riff: &reader.riff_chunk,
+        fmt: &reader.fmt_chunk,
+        fact: reader.fact_chunk.as_ref(),
+        other_chunks: reader
+            .other_chunks
+            .iter()
+            .map(|chunk| JsonChunk {
+                id: &chunk.id,
+                data_size: chunk.data_size,
+            })
+            .collect(),
+        data: &reader.data_chunk,
+    };
+
+    Ok(serde_json::to_string_pretty(&info)?)
+}
+
+fn extract_channel(input: PathBuf, channel: u16, output: PathBuf) -> Result<(), Error> {
+    let mut reader = RiffWaveReader::open(input)?;
+
+    let num_channels = reader.fmt_chunk.num_channels;
+    let block_align = reader.fmt_chunk.block_align;
+
+    if channel >= num_channels {
+        bail!(
+            "Channel {} out of range, file has {} channels",
+            channel,
+            num_channels
+        );
+    }
+
+    if block_align == 0 || block_align % num_channels != 0 {
+        bail!(
+            "Block align {} is not a multiple of the channel count",
+            block_align
+        );
+    }
+
+    let bytes_per_sample = (block_align / num_channels) as usize;
+    let start = channel as usize * bytes_per_sample;
+
+    let data = reader.data()?.collect::<Vec<_>>();
+    let mut channel_data = data
+        .chunks_exact(block_align as usize)
+        .flat_map(|frame| &frame[start..start + bytes_per_sample])
+        .copied()
+        .collect::<Vec<_>>();
+
+    to_little_endian(
+        reader.endianness,
+        &reader.fmt_chunk,
+        &mut channel_data,
+        bytes_per_sample,
+    )?;
+
+    let sample_rate = reader.fmt_chunk.sample_rate;
+
+    let fmt_chunk = FmtChunk {
+        id: FourCC::Fmt,
+        data_size: 16,
+        format: reader.fmt_chunk.effective_format(),
+        num_channels: 1,
+        sample_rate,
+        byte_rate: sample_rate.saturating_mul(bytes_per_sample as u32),
+        block_align: bytes_per_sample as u16,
+        bits_per_raw_sample: reader.fmt_chunk.bits_per_raw_sample,
+        extra_info_size: 0,
+        extended_info: None,
+    };
+
+    let mut writer = RiffWaveWriter::new(BufWriter::new(File::create(output)?), fmt_chunk)?;
+    writer.write_data(&channel_data)?;
+    writer.finalize()?;
+
+    Ok(())
+}
+
+/// Byte swaps the samples of a RIFX file, since the writer only writes
+/// little endian files.
+fn to_little_endian(
+    endianness: Endianness,
+    fmt_chunk: &FmtChunk,
+    data: &mut [u8],
+    bytes_per_sample: usize,
+) -> Result<(), Error> {
+    if endianness == Endianness::Little {
+        return Ok(());
+    }
+
+    match fmt_chunk.effective_format() {
+        Format::UncompressedPCM
+        | Format::IeeeFloatingPoint
+        | Format::G711ALaw
+        | Format::G711ULaw => {}
+        format => bail!("Can't convert big endian {} data to little endian", format),
+    }
+
+    for sample in data.chunks_exact_mut(bytes_per_sample) {
+        sample.reverse();
+    }
+
+    Ok(())
+}
+
+fn trim(input: PathBuf, start_secs: f64, end_secs: f64, output: PathBuf) -> Result<(), Error> {
+    let mut reader = RiffWaveReader::open(input)?;
+
+    let end_secs = end_secs.min(reader.duration().as_secs_f64());
+
+    if start_secs < 0.0 || start_secs >= end_secs {
+        bail!(
+            "Start {}s must be before the end {}s and not negative",
+            start_secs,
+            end_secs
+        );
+    }
+
+    let sample_rate = reader.fmt_chunk.sample_rate as f64;
+    let start_frame = (start_secs * sample_rate).floor() as u64;
+    let end_frame = ((end_secs * sample_rate).ceil() as u64).min(reader.num_frames());
+
+    let num_channels = reader.fmt_chunk.num_channels;
+    let block_align = reader.fmt_chunk.block_align;
+
+    if num_channels == 0 || block_align % num_channels != 0 {
+        bail!(
+            "Block align {} is not a multiple of the channel count",
+            block_align
+        );
+    }
+
+    let size = (end_frame - start_frame) * block_align as u64;
+
+    reader.seek_to_frame(start_frame)?;
+
+    let endianness = reader.endianness;
+    let (file, _, fmt_chunk, ..) = reader.into_parts();
+
+    let mut data = Vec::with_capacity(size as usize);
+    file.take(size).read_to_end(&mut data)?;
+
+    to_little_endian(
+        endianness,
+        &fmt_chunk,
+        &mut data,
+        (block_align / num_channels) as usize,
+    )?;
+
+    let mut writer = RiffWaveWriter::new(BufWriter::new(File::create(output)?), fmt_chunk)?;
+    writer.write_data(&data)?;
+    writer.finalize()?;
+
+    Ok(())
+}
+
+fn summary(input: PathBuf) -> Result<(), Error> {
+    let mut reader = RiffWaveReader::open(input)?;
+
+    let channels = match channel_summaries(&mut reader)? {
+        Some(channels) => channels,
+        None => {
+            println!(
+                "Summary unavailable for {} with {} bits per sample",
+                reader.fmt_chunk.effective_format(),
+                reader.fmt_chunk.bits_per_raw_sample
+            );
+            return Ok(());
+        }
+    };
+
+    for (channel, summary) in channels.iter().enumerate() {
+        println!(
+            "Channel {}: min {:.4} max {:.4} rms {:.4} peak {:.2} dBFS",
+            channel,
+            summary.min,
+            summary.max,
+            summary.rms(),
+            summary.peak_dbfs()
+        );
     }
 
     Ok(())
 }
 
+/// Summarizes the samples of each channel, or returns `None` if the samples
+/// can't be decoded.
+fn channel_summaries<T: Read + Seek>(
+    reader: &mut RiffWaveReader<T>,
+) -> Result<Option<Vec<ChannelSummary>>, Error> {
+    let num_channels = reader.fmt_chunk.num_channels.max(1) as usize;
+
+    let samples = match reader.samples_normalized() {
+        Ok(samples) => samples,
+        Err(RiffError::UnsupportedFormat(_)) | Err(RiffError::UnsupportedBitDepth(_)) => {
+            return Ok(None)
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut channels = vec![ChannelSummary::default(); num_channels];
+
+    for (index, sample) in samples.enumerate() {
+        channels[index % num_channels].add(sample);
+    }
+
+    Ok(Some(channels))
+}
+
+#[derive(Clone, Default)]
+struct ChannelSummary {
+    min: f32,
+    max: f32,
+    sum_squares: f64,
+    count: u64,
+}
+
+impl ChannelSummary {
+    fn add(&mut self, sample: f32) {
+        if self.count == 0 {
+            self.min = sample;
+            self.max = sample;
+        }
+
+        self.min = self.min.min(sample);
+        self.max = self.max.max(sample);
+        self.sum_squares += sample as f64 * sample as f64;
+        self.count += 1;
+    }
+
+    fn rms(&self) -> f64 {
+        if self.count == 0 {
+            return 0.0;
+        }
+
+        (self.sum_squares / self.count as f64).sqrt()
+    }
+
+    fn peak_dbfs(&self) -> f64 {
+        let peak = self.min.abs().max(self.max.abs()) as f64;
+
+        20.0 * peak.log10()
+    }
+}
+
 #[derive(StructOpt)]
 #[structopt(name = "riff-cli")]
 struct Opts {
@@ -50,4 +311,258 @@ enum Command {
         #[structopt(parse(from_os_str))]
         input: PathBuf,
     },
+    Json {
+        #[structopt(parse(from_os_str))]
+        input: PathBuf,
+    },
+    /// Writes a single channel of the input to a new mono file
+    ExtractChannel {
+        #[structopt(parse(from_os_str))]
+        input: PathBuf,
+        /// Zero based index of the channel to extract
+        channel: u16,
+        #[structopt(parse(from_os_str))]
+        output: PathBuf,
+    },
+    /// Writes the time range between start and end to a new file
+    Trim {
+        #[structopt(parse(from_os_str))]
+        input: PathBuf,
+        start_secs: f64,
+        end_secs: f64,
+        #[structopt(parse(from_os_str))]
+        output: PathBuf,
+    },
+    /// Prints the min, max, RMS and peak level of each channel
+    Summary {
+        #[structopt(parse(from_os_str))]
+        input: PathBuf,
+    },
+}
+
+#[derive(Serialize)]
+struct JsonInfo<'a> {
+    riff: &'a RiffChunk,
+    fmt: &'a FmtChunk,
+    fact: Option<&'a FactChunk>,
+    other_chunks: Vec<JsonChunk<'a>>,
+    data: &'a DataChunk,
+}
+
+#[derive(Serialize)]
+struct JsonChunk<'a> {
+    id: &'a FourCC,
+    data_size: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::Cursor;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("riff-cli-{}-{}", std::process::id(), name))
+    }
+
+    fn wav_bytes(fmt_chunk: FmtChunk, data: &[u8]) -> Vec<u8> {
+        let mut writer = RiffWaveWriter::new(Cursor::new(vec![]), fmt_chunk).unwrap();
+        writer.write_data(data).unwrap();
+        writer.finalize().unwrap().into_inner()
+    }
+
+    #[test]
+    fn json_prints_parsed_chunks() {
+        let bytes = wav_bytes(FmtChunk::pcm(2, 44100, 16), &[0; 8]);
+        let mut reader = RiffWaveReader::from_bytes(bytes).unwrap();
+
+        let path = temp_path("json.wav");
+        let title = (FourCC::Other(String::from("INAM")), String::from("Title"));
+        reader
+            .write_with_info(File::create(&path).unwrap(), &[title])
+            .unwrap();
+
+        let output = json(path.clone());
+        std::fs::remove_file(&path).unwrap();
+
+        let value: serde_json::Value = serde_json::from_str(&output.unwrap()).unwrap();
+        assert_eq!(value["riff"]["id"], "RIFF");
+        assert_eq!(value["fmt"]["format"]["name"], "UncompressedPCM");
+        assert_eq!(value["fmt"]["format"]["tag"], 1);
+        assert_eq!(value["fmt"]["num_channels"], 2);
+        assert_eq!(value["fmt"]["sample_rate"], 44100);
+        assert_eq!(value["fact"], serde_json::Value::Null);
+        assert_eq!(value["other_chunks"][0]["id"], "LIST");
+        assert_eq!(value["other_chunks"][0]["data_size"], 18);
+        assert_eq!(value["data"]["data_size"], 8);
+    }
+
+    fn rifx_bytes(num_channels: u16, sample_rate: u32, samples: &[i16]) -> Vec<u8> {
+        let block_align = num_channels * 2;
+        let data_size = samples.len() as u32 * 2;
+
+        let mut bytes = b"RIFX".to_vec();
+        bytes.extend_from_slice(&(36 + data_size).to_be_bytes());
+        bytes.extend_from_slice(b"WAVEfmt ");
+        bytes.extend_from_slice(&16u32.to_be_bytes());
+        bytes.extend_from_slice(&1u16.to_be_bytes());
+        bytes.extend_from_slice(&num_channels.to_be_bytes());
+        bytes.extend_from_slice(&sample_rate.to_be_bytes());
+        bytes.extend_from_slice(&(sample_rate * block_align as u32).to_be_bytes());
+        bytes.extend_from_slice(&block_align.to_be_bytes());
+        bytes.extend_from_slice(&16u16.to_be_bytes());
+        bytes.extend_from_slice(b"data");
+        bytes.extend_from_slice(&data_size.to_be_bytes());
+        bytes.extend(samples.iter().flat_map(|sample| sample.to_be_bytes()));
+        bytes
+    }
+
+    fn pcm16(samples: &[i16]) -> Vec<u8> {
+        samples
+            .iter()
+            .flat_map(|sample| sample.to_le_bytes())
+            .collect()
+    }
+
+    #[test]
+    fn extract_channel_writes_mono_file() {
+        let input = temp_path("extract-input.wav");
+        let output = temp_path("extract-output.wav");
+        let bytes = wav_bytes(FmtChunk::pcm(2, 8000, 16), &pcm16(&[1, -1, 2, -2, 3, -3]));
+        std::fs::write(&input, bytes).unwrap();
+
+        let result = extract_channel(input.clone(), 1, output.clone());
+        let reader = RiffWaveReader::open(&output);
+        std::fs::remove_file(&input).unwrap();
+        std::fs::remove_file(&output).unwrap();
+        result.unwrap();
+
+        let mut reader = reader.unwrap();
+        assert_eq!(reader.fmt_chunk, FmtChunk::pcm(1, 8000, 16));
+        assert_eq!(
+            reader.samples_i16().unwrap().collect::<Vec<_>>(),
+            [-1, -2, -3]
+        );
+    }
+
+    #[test]
+    fn extract_channel_byte_swaps_rifx_samples() {
+        let input = temp_path("extract-rifx-input.wav");
+        let output = temp_path("extract-rifx-output.wav");
+        std::fs::write(&input, rifx_bytes(2, 8000, &[0x1234, 0x5678])).unwrap();
+
+        let result = extract_channel(input.clone(), 0, output.clone());
+        let reader = RiffWaveReader::open(&output);
+        std::fs::remove_file(&input).unwrap();
+        std::fs::remove_file(&output).unwrap();
+        result.unwrap();
+
+        let mut reader = reader.unwrap();
+        assert_eq!(reader.endianness, Endianness::Little);
+        assert_eq!(reader.samples_i16().unwrap().collect::<Vec<_>>(), [0x1234]);
+    }
+
+    #[test]
+    fn extract_channel_rejects_out_of_range_channel() {
+        let input = temp_path("extract-range-input.wav");
+        let output = temp_path("extract-range-output.wav");
+        std::fs::write(&input, wav_bytes(FmtChunk::pcm(2, 8000, 16), &[0; 4])).unwrap();
+
+        let result = extract_channel(input.clone(), 2, output.clone());
+        std::fs::remove_file(&input).unwrap();
+
+        assert!(result.is_err());
+        assert!(!output.exists());
+    }
+
+    #[test]
+    fn trim_writes_middle_of_file() {
+        let input = temp_path("trim-input.wav");
+        let output = temp_path("trim-output.wav");
+        // 2 seconds of 100 Hz stereo with each sample holding its frame index
+        let samples = (0..200)
+            .flat_map(|frame| [frame, -frame])
+            .collect::<Vec<_>>();
+        std::fs::write(
+            &input,
+            wav_bytes(FmtChunk::pcm(2, 100, 16), &pcm16(&samples)),
+        )
+        .unwrap();
+
+        let result = trim(input.clone(), 0.5, 1.5, output.clone());
+        let reader = RiffWaveReader::open(&output);
+        std::fs::remove_file(&input).unwrap();
+        std::fs::remove_file(&output).unwrap();
+        result.unwrap();
+
+        let mut reader = reader.unwrap();
+        assert_eq!(reader.fmt_chunk, FmtChunk::pcm(2, 100, 16));
+        assert_eq!(reader.duration(), std::time::Duration::from_secs(1));
+        assert_eq!(
+            reader.samples_i16().unwrap().take(2).collect::<Vec<_>>(),
+            [50, -50]
+        );
+    }
+
+    #[test]
+    fn trim_byte_swaps_rifx_samples() {
+        let input = temp_path("trim-rifx-input.wav");
+        let output = temp_path("trim-rifx-output.wav");
+        std::fs::write(&input, rifx_bytes(1, 4, &[1, 0x1234, 0x5678, 4])).unwrap();
+
+        let result = trim(input.clone(), 0.25, 0.75, output.clone());
+        let reader = RiffWaveReader::open(&output);
+        std::fs::remove_file(&input).unwrap();
+        std::fs::remove_file(&output).unwrap();
+        result.unwrap();
+
+        let mut reader = reader.unwrap();
+        assert_eq!(reader.endianness, Endianness::Little);
+        assert_eq!(
+            reader.samples_i16().unwrap().collect::<Vec<_>>(),
+            [0x1234, 0x5678]
+        );
+    }
+
+    #[test]
+    fn trim_rejects_start_after_end() {
+        let input = temp_path("trim-range-input.wav");
+        let output = temp_path("trim-range-output.wav");
+        std::fs::write(&input, wav_bytes(FmtChunk::pcm(1, 100, 16), &[0; 200])).unwrap();
+
+        let result = trim(input.clone(), 0.8, 0.5, output.clone());
+        std::fs::remove_file(&input).unwrap();
+
+        assert!(result.is_err());
+        assert!(!output.exists());
+    }
+
+    #[test]
+    fn summary_reports_min_max_and_peak_of_each_channel() {
+        let samples = [8192, -16384, 16384, -8192, 4096, -4096];
+        let bytes = wav_bytes(FmtChunk::pcm(2, 8000, 16), &pcm16(&samples));
+        let mut reader = RiffWaveReader::from_bytes(bytes).unwrap();
+
+        let channels = channel_summaries(&mut reader).unwrap().unwrap();
+
+        assert_eq!(channels.len(), 2);
+        assert_eq!(channels[0].min, 0.125);
+        assert_eq!(channels[0].max, 0.5);
+        assert_eq!(channels[1].min, -0.5);
+        assert_eq!(channels[1].max, -0.125);
+        assert!((channels[0].peak_dbfs() - 20.0 * 0.5f64.log10()).abs() < 1e-9);
+        assert!((channels[1].peak_dbfs() - 20.0 * 0.5f64.log10()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn summary_is_unavailable_for_undecodable_formats() {
+        let fmt_chunk = FmtChunk {
+            format: Format::Other(0x55),
+            ..FmtChunk::pcm(1, 8000, 16)
+        };
+        let bytes = wav_bytes(fmt_chunk, &[0; 4]);
+        let mut reader = RiffWaveReader::from_bytes(bytes).unwrap();
+
+        assert!(channel_summaries(&mut reader).unwrap().is_none());
+    }
 }
@@ -0,0 +1,125 @@
+use std::io::Cursor;
+
+use crate::{Endianness, Error, ParseKind, ReadExt};
+
+const FLAG_ONE_SHOT: u32 = 0x01;
+const FLAG_ROOT_NOTE: u32 = 0x02;
+const FLAG_STRETCH: u32 = 0x04;
+const FLAG_DISK_BASED: u32 = 0x08;
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AcidChunk {
+    pub flags: u32,
+    /// MIDI note number of the root note, 60 being middle C.
+    pub root_note: u16,
+    pub num_beats: u32,
+    pub meter_denominator: u16,
+    pub meter_numerator: u16,
+    /// Tempo in beats per minute.
+    pub tempo: f32,
+}
+
+impl AcidChunk {
+    /// Returns true for a one-shot, false for a loop.
+    pub fn is_one_shot(&self) -> bool {
+        self.flags & FLAG_ONE_SHOT != 0
+    }
+
+    pub fn has_root_note(&self) -> bool {
+        self.flags & FLAG_ROOT_NOTE != 0
+    }
+
+    pub fn is_stretched(&self) -> bool {
+        self.flags & FLAG_STRETCH != 0
+    }
+
+    pub fn is_disk_based(&self) -> bool {
+        self.flags & FLAG_DISK_BASED != 0
+    }
+}
+
+pub(crate) fn read_acid_chunk(
+    data: &[u8],
+    offset: u64,
+    endianness: Endianness,
+) -> Result<AcidChunk, Error> {
+    if data.len() < 24 {
+        return Err(Error::parse(offset, ParseKind::InvalidAcidChunk));
+    }
+
+    let mut reader = Cursor::new(data);
+
+    let flags = reader.read_u32(endianness)?;
+    let root_note = reader.read_u16(endianness)?;
+
+    // Two reserved fields, a u16 and an f32
+    reader.read_bytes(6)?;
+
+    let num_beats = reader.read_u32(endianness)?;
+    let meter_denominator = reader.read_u16(endianness)?;
+    let meter_numerator = reader.read_u16(endianness)?;
+    let tempo = f32::from_bits(reader.read_u32(endianness)?);
+
+    Ok(AcidChunk {
+        flags,
+        root_note,
+        num_beats,
+        meter_denominator,
+        meter_numerator,
+        tempo,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn acid_data(flags: u32, root_note: u16, num_beats: u32, tempo: f32) -> Vec<u8> {
+        let mut data = flags.to_le_bytes().to_vec();
+        data.extend_from_slice(&root_note.to_le_bytes());
+        data.extend_from_slice(&[0; 6]);
+        data.extend_from_slice(&num_beats.to_le_bytes());
+        data.extend_from_slice(&4u16.to_le_bytes());
+        data.extend_from_slice(&4u16.to_le_bytes());
+        data.extend_from_slice(&tempo.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn tempo_and_root_note_are_read() {
+        let data = acid_data(FLAG_ROOT_NOTE | FLAG_STRETCH, 57, 8, 128.5);
+
+        let acid = read_acid_chunk(&data, 0, Endianness::Little).unwrap();
+
+        assert_eq!(acid.tempo, 128.5);
+        assert_eq!(acid.root_note, 57);
+        assert_eq!(acid.num_beats, 8);
+        assert_eq!(acid.meter_numerator, 4);
+        assert_eq!(acid.meter_denominator, 4);
+        assert!(!acid.is_one_shot());
+        assert!(acid.has_root_note());
+        assert!(acid.is_stretched());
+        assert!(!acid.is_disk_based());
+    }
+
+    #[test]
+    fn one_shot_flag_is_decoded() {
+        let data = acid_data(FLAG_ONE_SHOT, 60, 0, 0.0);
+
+        assert!(read_acid_chunk(&data, 0, Endianness::Little)
+            .unwrap()
+            .is_one_shot());
+    }
+
+    #[test]
+    fn short_acid_chunk_is_invalid() {
+        assert!(matches!(
+            read_acid_chunk(&[0; 20], 36, Endianness::Little),
+            Err(Error::Parse {
+                offset: 36,
+                kind: ParseKind::InvalidAcidChunk
+            })
+        ));
+    }
+}
@@ -0,0 +1,186 @@
+use std::io::Cursor;
+
+use crate::{Endianness, Error, FourCC, ReadExt};
+
+/// An entry of an `adtl` list, attached to the cue point with the same id.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AdtlEntry {
+    pub cue_point_id: u32,
+    pub kind: AdtlKind,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AdtlKind {
+    Label(String),
+    Note(String),
+    LabeledText {
+        sample_length: u32,
+        purpose: FourCC,
+        country: u16,
+        language: u16,
+        dialect: u16,
+        code_page: u16,
+        text: String,
+    },
+}
+
+impl AdtlEntry {
+    pub fn text(&self) -> &str {
+        match &self.kind {
+            AdtlKind::Label(text) | AdtlKind::Note(text) => text,
+            AdtlKind::LabeledText { text, .. } => text,
+        }
+    }
+}
+
+pub(crate) fn read_adtl_entries(
+    list_data: &[u8],
+    endianness: Endianness,
+) -> Result<Option<Vec<AdtlEntry>>, Error> {
+    let mut reader = Cursor::new(list_data);
+
+    match reader.read_fourcc_or_eof()? {
+        Some(FourCC::Other(list_type)) if list_type == "adtl" => {}
+        _ => return Ok(None),
+    }
+
+    let mut entries = vec![];
+
+    while let Some(id) = reader.read_fourcc_or_eof()? {
+        let data_size = reader.read_u32(endianness)?;
+        if data_size as u64 > list_data.len() as u64 - reader.position() {
+            return Err(Error::InvalidListChunk);
+        }
+
+        let end = reader.position() + data_size as u64;
+
+        let id = match &id {
+            FourCC::Other(id) => id.as_str(),
+            _ => "",
+        };
+
+        let kind = match id {
+            "labl" | "note" if data_size >= 4 => {
+                let cue_point_id = reader.read_u32(endianness)?;
+                let text = reader.read_string(data_size as usize - 4)?;
+
+                let kind = if id == "labl" {
+                    AdtlKind::Label(text)
+                } else {
+                    AdtlKind::Note(text)
+                };
+
+                Some((cue_point_id, kind))
+            }
+            "ltxt" if data_size >= 20 => {
+                let cue_point_id = reader.read_u32(endianness)?;
+                let sample_length = reader.read_u32(endianness)?;
+                let purpose = reader.read_fourcc()?;
+                let country = reader.read_u16(endianness)?;
+                let language = reader.read_u16(endianness)?;
+                let dialect = reader.read_u16(endianness)?;
+                let code_page = reader.read_u16(endianness)?;
+                let text = reader.read_string(data_size as usize - 20)?;
+
+                let kind = AdtlKind::LabeledText {
+                    sample_length,
+                    purpose,
+                    country,
+                    language,
+                    dialect,
+                    code_page,
+                    text,
+                };
+
+                Some((cue_point_id, kind))
+            }
+            _ => None,
+        };
+
+        if let Some((cue_point_id, kind)) = kind {
+            entries.push(AdtlEntry { cue_point_id, kind });
+        }
+
+        reader.set_position(end + (data_size % 2) as u64);
+    }
+
+    Ok(Some(entries))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture::list_data;
+
+    fn text_body(cue_point_id: u32, text: &[u8]) -> Vec<u8> {
+        let mut body = cue_point_id.to_le_bytes().to_vec();
+        body.extend_from_slice(text);
+        body
+    }
+
+    #[test]
+    fn labels_notes_and_labeled_text_are_read() {
+        let mut ltxt = 2u32.to_le_bytes().to_vec();
+        ltxt.extend_from_slice(&4410u32.to_le_bytes());
+        ltxt.extend_from_slice(b"rgn ");
+        ltxt.extend_from_slice(&[1, 0, 9, 0, 0, 0, 0xe4, 0x04]);
+        ltxt.extend_from_slice(b"Chorus\0");
+
+        let data = list_data(
+            Endianness::Little,
+            b"adtl",
+            &[
+                (b"labl", &text_body(1, b"Verse\0")),
+                (b"note", &text_body(1, b"Quiet\0")),
+                (b"ltxt", &ltxt),
+            ],
+        );
+
+        let entries = read_adtl_entries(&data, Endianness::Little)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].cue_point_id, 1);
+        assert!(matches!(&entries[0].kind, AdtlKind::Label(text) if text == "Verse"));
+        assert!(matches!(&entries[1].kind, AdtlKind::Note(text) if text == "Quiet"));
+        assert_eq!(entries[2].cue_point_id, 2);
+        assert!(matches!(
+            &entries[2].kind,
+            AdtlKind::LabeledText {
+                sample_length: 4410,
+                country: 1,
+                language: 9,
+                code_page: 1252,
+                text,
+                ..
+            } if text == "Chorus"
+        ));
+    }
+
+    #[test]
+    fn other_list_types_are_ignored() {
+        let data = list_data(Endianness::Little, b"INFO", &[(b"INAM", b"Name\0")]);
+
+        assert!(read_adtl_entries(&data, Endianness::Little)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn sub_chunk_exceeding_list_is_invalid() {
+        let mut data = list_data(
+            Endianness::Little,
+            b"adtl",
+            &[(b"labl", &text_body(1, b"A\0"))],
+        );
+        data.truncate(data.len() - 2);
+
+        assert!(matches!(
+            read_adtl_entries(&data, Endianness::Little),
+            Err(Error::InvalidListChunk)
+        ));
+    }
+}
@@ -0,0 +1,289 @@
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt};
+
+use std::io;
+use std::io::Cursor;
+use std::io::SeekFrom;
+
+use crate::source::SourceExt;
+use crate::{
+    check_chunk_size, AcidChunk, AdtlEntry, BextChunk, CartChunk, ChunkHeader, CuePoint, DataChunk,
+    Ds64Chunk, Endianness, Error, FactChunk, FmtChunk, FourCC, HeaderError, Metadata, OtherChunk,
+    ParseKind, PeakChunk, ReadExt, RiffChunk, SliceSource, SmplChunk, WaveSegment,
+    DEFAULT_MAX_CHUNK_SIZE,
+};
+
+/// Async counterpart of [`RiffWaveReader`](crate::RiffWaveReader) for
+/// `tokio` readers.
+///
+/// Each chunk is read asynchronously and then parsed in memory, so the fields
+/// and errors are the same as for the blocking reader.
+#[derive(Debug)]
+pub struct AsyncRiffWaveReader<T: AsyncRead + AsyncSeek + Unpin> {
+    reader: T,
+    pub riff_chunk: RiffChunk,
+    pub fmt_chunk: FmtChunk,
+    pub fact_chunk: Option<FactChunk>,
+    pub ds64_chunk: Option<Ds64Chunk>,
+    pub data_chunk: DataChunk,
+    pub other_chunks: Vec<OtherChunk>,
+    pub bext: Option<BextChunk>,
+    pub cart: Option<CartChunk>,
+    pub cue_points: Option<Vec<CuePoint>>,
+    pub adtl: Option<Vec<AdtlEntry>>,
+    pub smpl: Option<SmplChunk>,
+    pub peak: Option<PeakChunk>,
+    pub ixml: Option<String>,
+    pub acid: Option<AcidChunk>,
+    pub id3: Option<Vec<u8>>,
+    pub wave_segments: Option<Vec<WaveSegment>>,
+    pub endianness: Endianness,
+    data_offset: u64,
+}
+
+impl<T: AsyncRead + AsyncSeek + Unpin> AsyncRiffWaveReader<T> {
+    pub async fn new(mut reader: T) -> Result<AsyncRiffWaveReader<T>, Error> {
+        let riff_offset = reader.stream_position().await?;
+
+        let mut buf = [0; 12];
+        reader
+            .read_exact(&mut buf)
+            .await
+            .map_err(|e| Error::from(e).eof_context(riff_offset, "riff header"))?;
+        let riff_chunk = parse_body(&buf, riff_offset, "riff header", |source| {
+            source.read_riff_chunk()
+        })?;
+
+        let is_64 = match riff_chunk.id {
+            FourCC::Riff | FourCC::Rifx => false,
+            FourCC::Rf64 | FourCC::Bw64 => true,
+            _ => return Err(Error::parse(riff_offset, ParseKind::NotRiff)),
+        };
+
+        if riff_chunk.file_type != FourCC::Wave {
+            return Err(Error::parse(riff_offset + 8, ParseKind::NotWave));
+        }
+
+        let endianness = riff_chunk.endianness();
+
+        let ds64_chunk = if is_64 {
+            let header = read_chunk_header(&mut reader, endianness).await?;
+
+            match header {
+                Some(header) if header.id == FourCC::Ds64 => {
+                    let body = read_chunk_body(&mut reader, &header).await?;
+                    Some(parse_body(&body, header.offset, "ds64 chunk", |source| {
+                        source.read_ds64_chunk(header, endianness)
+                    })?)
+                }
+                _ => return Err(Error::parse(riff_offset + 12, ParseKind::InvalidDs64Chunk)),
+            }
+        } else {
+            None
+        };
+
+        let mut fmt_chunk = None;
+        let mut fact_chunk = None;
+        let mut other_chunks = vec![];
+        let mut metadata = Metadata::default();
+
+        let mut data_chunk = loop {
+            let offset = reader.stream_position().await?;
+
+            let header = match read_chunk_header(&mut reader, endianness).await? {
+                Some(header) => header,
+                None if metadata.wave_segments.is_some() => {
+                    if fmt_chunk.is_none() {
+                        return Err(Error::parse(offset, ParseKind::MissingFmtChunk));
+                    }
+
+                    break DataChunk::empty();
+                }
+                None => return Err(Error::parse(offset, ParseKind::MissingDataChunk)),
+            };
+
+            if header.id == FourCC::Data {
+                if fmt_chunk.is_none() {
+                    return Err(Error::parse(offset, ParseKind::MissingFmtChunk));
+                }
+
+                break DataChunk::from_header(&header);
+            }
+
+            let body = read_chunk_body(&mut reader, &header).await?;
+
+            match header.id {
+                FourCC::Fmt => {
+                    fmt_chunk = Some(parse_body(&body, offset, "fmt chunk", |source| {
+                        source.read_fmt_chunk(header, endianness)
+                    })?)
+                }
+                FourCC::Fact => {
+                    fact_chunk = Some(parse_body(&body, offset, "fact chunk", |source| {
+                        source.read_fact_chunk(header, endianness)
+                    })?)
+                }
+                _ => {
+                    let chunk = Cursor::new(body).read_other_chunk(header)?;
+
+                    metadata.read(&chunk, offset, endianness);
+                    other_chunks.push(chunk);
+                }
+            }
+        };
+
+        metadata.finish(endianness, fmt_chunk.as_ref());
+
+        let fmt_chunk = fmt_chunk.unwrap();
+
+        if let Some(ds64_chunk) = &ds64_chunk {
+            if data_chunk.data_size == 0xffff_ffff {
+                data_chunk.data_size = ds64_chunk.data_chunk_size;
+                data_chunk.pad_byte = (ds64_chunk.data_chunk_size % 2) as u8;
+                data_chunk.is_unsized = false;
+            }
+        }
+
+        let data_offset = reader.stream_position().await?;
+
+        if data_chunk.is_unsized {
+            data_chunk.data_size = reader
+                .seek(SeekFrom::End(0))
+                .await?
+                .saturating_sub(data_offset);
+            data_chunk.pad_byte = 0;
+
+            reader.seek(SeekFrom::Start(data_offset)).await?;
+        }
+
+        Ok(AsyncRiffWaveReader {
+            reader,
+            riff_chunk,
+            fmt_chunk,
+            fact_chunk,
+            ds64_chunk,
+            data_chunk,
+            other_chunks,
+            bext: metadata.bext,
+            cart: metadata.cart,
+            cue_points: metadata.cue_points,
+            adtl: metadata.adtl,
+            smpl: metadata.smpl,
+            peak: metadata.peak,
+            ixml: metadata.ixml,
+            acid: metadata.acid,
+            id3: metadata.id3,
+            wave_segments: metadata.wave_segments,
+            endianness,
+            data_offset,
+        })
+    }
+
+    pub fn data_offset(&self) -> u64 {
+        self.data_offset
+    }
+
+    /// Returns the underlying reader, positioned at the start of the data block
+    /// unless it has been moved since.
+    pub fn into_reader(self) -> T {
+        self.reader
+    }
+}
+
+async fn read_chunk_header<T: AsyncRead + AsyncSeek + Unpin>(
+    reader: &mut T,
+    endianness: Endianness,
+) -> Result<Option<ChunkHeader>, Error> {
+    let offset = reader.stream_position().await?;
+
+    let mut buf = [0; 8];
+    match reader.read_exact(&mut buf[..4]).await {
+        Ok(_) => {}
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e.into()),
+    }
+    reader.read_exact(&mut buf[4..]).await?;
+
+    let header = parse_body(&buf, offset, "chunk header", |source| {
+        source.read_chunk_header(endianness)
+    })?;
+
+    Ok(header.map(|header| ChunkHeader { offset, ..header }))
+}
+
+/// Reads the body of a chunk into memory and skips the pad byte, so it can be
+/// parsed with the blocking parsers.
+async fn read_chunk_body<T: AsyncRead + AsyncSeek + Unpin>(
+    reader: &mut T,
+    header: &ChunkHeader,
+) -> Result<Vec<u8>, Error> {
+    check_chunk_size(header.data_size, DEFAULT_MAX_CHUNK_SIZE)?;
+
+    let mut body = vec![0; header.data_size as usize];
+    reader
+        .read_exact(&mut body)
+        .await
+        .map_err(|e| Error::from(e).eof_context(header.offset, "chunk body"))?;
+
+    if header.data_size % 2 == 1 {
+        reader.seek(SeekFrom::Current(1)).await?;
+    }
+
+    Ok(body)
+}
+
+/// Runs a [`SourceExt`] parser on bytes read into memory, reporting its
+/// errors at `offset`.
+fn parse_body<V>(
+    body: &[u8],
+    offset: u64,
+    context: &'static str,
+    parse: impl FnOnce(&mut SliceSource<'_>) -> Result<V, HeaderError>,
+) -> Result<V, Error> {
+    parse(&mut SliceSource::new(body)).map_err(|e| Error::from_header(e, offset, context))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture::WavBuilder;
+    use crate::RiffWaveReader;
+
+    #[tokio::test]
+    async fn fields_match_blocking_reader() {
+        let bytes = WavBuilder::new()
+            .fmt(2, 44100, 16)
+            .chunk(b"LIST", b"INFO")
+            .data(&[1, 2, 3, 4])
+            .build();
+
+        let reader = AsyncRiffWaveReader::new(Cursor::new(bytes.clone()))
+            .await
+            .unwrap();
+        let blocking = RiffWaveReader::from_bytes(bytes).unwrap();
+
+        assert_eq!(reader.riff_chunk, blocking.riff_chunk);
+        assert_eq!(reader.fmt_chunk, blocking.fmt_chunk);
+        assert_eq!(reader.other_chunks, blocking.other_chunks);
+        assert_eq!(reader.data_chunk, blocking.data_chunk);
+        assert_eq!(reader.data_offset(), blocking.data_offset());
+
+        let mut data = vec![];
+        reader.into_reader().read_to_end(&mut data).await.unwrap();
+        assert_eq!(data, [1, 2, 3, 4]);
+    }
+
+    #[tokio::test]
+    async fn missing_data_chunk_is_reported() {
+        let bytes = WavBuilder::new().fmt(1, 8000, 8).build();
+
+        let result = AsyncRiffWaveReader::new(Cursor::new(bytes)).await;
+
+        assert!(matches!(
+            result,
+            Err(Error::Parse {
+                offset: 36,
+                kind: ParseKind::MissingDataChunk
+            })
+        ));
+    }
+}
@@ -0,0 +1,120 @@
+use std::io::Cursor;
+
+use crate::{Endianness, Error, ParseKind, ReadExt};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BextChunk {
+    pub description: String,
+    pub originator: String,
+    pub originator_reference: String,
+    pub origination_date: String,
+    pub origination_time: String,
+    pub time_reference: u64,
+    pub version: u16,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::bytes"))]
+    pub umid: Vec<u8>,
+    pub loudness_value: i16,
+    pub loudness_range: i16,
+    pub max_true_peak_level: i16,
+    pub max_momentary_loudness: i16,
+    pub max_short_term_loudness: i16,
+    pub coding_history: String,
+}
+
+pub(crate) fn read_bext_chunk(
+    data: &[u8],
+    offset: u64,
+    endianness: Endianness,
+) -> Result<BextChunk, Error> {
+    if data.len() < 602 {
+        return Err(Error::parse(offset, ParseKind::InvalidBextChunk));
+    }
+
+    let mut reader = Cursor::new(data);
+
+    let description = reader.read_string(256)?;
+    let originator = reader.read_string(32)?;
+    let originator_reference = reader.read_string(32)?;
+    let origination_date = reader.read_string(10)?;
+    let origination_time = reader.read_string(8)?;
+    let time_reference_low = reader.read_u32(endianness)? as u64;
+    let time_reference_high = reader.read_u32(endianness)? as u64;
+    let version = reader.read_u16(endianness)?;
+    let umid = reader.read_bytes(64)?;
+    let loudness_value = reader.read_u16(endianness)? as i16;
+    let loudness_range = reader.read_u16(endianness)? as i16;
+    let max_true_peak_level = reader.read_u16(endianness)? as i16;
+    let max_momentary_loudness = reader.read_u16(endianness)? as i16;
+    let max_short_term_loudness = reader.read_u16(endianness)? as i16;
+    reader.read_bytes(180)?;
+    let coding_history = reader.read_string(data.len() - 602)?;
+
+    Ok(BextChunk {
+        description,
+        originator,
+        originator_reference,
+        origination_date,
+        origination_time,
+        time_reference: time_reference_high << 32 | time_reference_low,
+        version,
+        umid,
+        loudness_value,
+        loudness_range,
+        max_true_peak_level,
+        max_momentary_loudness,
+        max_short_term_loudness,
+        coding_history,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bext_data(coding_history: &[u8]) -> Vec<u8> {
+        let mut data = vec![0; 602];
+        data[..11].copy_from_slice(b"Description");
+        data[256..266].copy_from_slice(b"Originator");
+        data[320..330].copy_from_slice(b"2020-01-02");
+        data[330..338].copy_from_slice(b"03:04:05");
+        // time_reference as low and high u32
+        data[338..342].copy_from_slice(&2u32.to_le_bytes());
+        data[342..346].copy_from_slice(&1u32.to_le_bytes());
+        data[346..348].copy_from_slice(&2u16.to_le_bytes());
+        data[412..414].copy_from_slice(&(-2300i16).to_le_bytes());
+        data.extend_from_slice(coding_history);
+        data
+    }
+
+    #[test]
+    fn bext_chunk_is_read() {
+        let data = bext_data(b"A=PCM,F=48000\r\n\0");
+
+        let bext = read_bext_chunk(&data, 0, Endianness::Little).unwrap();
+
+        assert_eq!(bext.description, "Description");
+        assert_eq!(bext.originator, "Originator");
+        assert_eq!(bext.originator_reference, "");
+        assert_eq!(bext.origination_date, "2020-01-02");
+        assert_eq!(bext.origination_time, "03:04:05");
+        assert_eq!(bext.time_reference, (1 << 32) | 2);
+        assert_eq!(bext.version, 2);
+        assert_eq!(bext.umid, [0; 64]);
+        assert_eq!(bext.loudness_value, -2300);
+        assert_eq!(bext.coding_history, "A=PCM,F=48000\r\n");
+    }
+
+    #[test]
+    fn short_bext_chunk_is_invalid() {
+        let data = vec![0; 601];
+
+        assert!(matches!(
+            read_bext_chunk(&data, 12, Endianness::Little),
+            Err(Error::Parse {
+                offset: 12,
+                kind: ParseKind::InvalidBextChunk
+            })
+        ));
+    }
+}
@@ -0,0 +1,156 @@
+use std::io::Cursor;
+
+use crate::{Endianness, Error, FourCC, ParseKind, ReadExt};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CartChunk {
+    pub version: String,
+    pub title: String,
+    pub artist: String,
+    pub cut_id: String,
+    pub client_id: String,
+    pub category: String,
+    pub classification: String,
+    pub out_cue: String,
+    pub start_date: String,
+    pub start_time: String,
+    pub end_date: String,
+    pub end_time: String,
+    pub producer_app_id: String,
+    pub producer_app_version: String,
+    pub user_def: String,
+    pub level_reference: i32,
+    pub post_timers: Vec<CartTimer>,
+    pub url: String,
+    pub tag_text: String,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CartTimer {
+    pub usage: FourCC,
+    pub value: u32,
+}
+
+pub(crate) fn read_cart_chunk(
+    data: &[u8],
+    offset: u64,
+    endianness: Endianness,
+) -> Result<CartChunk, Error> {
+    if data.len() < 2048 {
+        return Err(Error::parse(offset, ParseKind::InvalidCartChunk));
+    }
+
+    let mut reader = Cursor::new(data);
+
+    let version = read_text(&mut reader, 4)?;
+    let title = read_text(&mut reader, 64)?;
+    let artist = read_text(&mut reader, 64)?;
+    let cut_id = read_text(&mut reader, 64)?;
+    let client_id = read_text(&mut reader, 64)?;
+    let category = read_text(&mut reader, 64)?;
+    let classification = read_text(&mut reader, 64)?;
+    let out_cue = read_text(&mut reader, 64)?;
+    let start_date = read_text(&mut reader, 10)?;
+    let start_time = read_text(&mut reader, 8)?;
+    let end_date = read_text(&mut reader, 10)?;
+    let end_time = read_text(&mut reader, 8)?;
+    let producer_app_id = read_text(&mut reader, 64)?;
+    let producer_app_version = read_text(&mut reader, 64)?;
+    let user_def = read_text(&mut reader, 64)?;
+    let level_reference = reader.read_u32(endianness)? as i32;
+
+    let mut post_timers = vec![];
+
+    for _ in 0..8 {
+        let usage = reader.read_bytes(4)?;
+        let value = reader.read_u32(endianness)?;
+
+        // Unused timers have a null usage id
+        if usage != [0; 4] {
+            post_timers.push(CartTimer {
+                usage: FourCC::from(&usage[..]),
+                value,
+            });
+        }
+    }
+
+    reader.read_bytes(276)?;
+    let url = read_text(&mut reader, 1024)?;
+    let tag_text = read_text(&mut reader, data.len() - 2048)?;
+
+    Ok(CartChunk {
+        version,
+        title,
+        artist,
+        cut_id,
+        client_id,
+        category,
+        classification,
+        out_cue,
+        start_date,
+        start_time,
+        end_date,
+        end_time,
+        producer_app_id,
+        producer_app_version,
+        user_def,
+        level_reference,
+        post_timers,
+        url,
+        tag_text,
+    })
+}
+
+/// Reads a fixed length text field, which may be padded with spaces or nulls.
+fn read_text(reader: &mut Cursor<&[u8]>, len: usize) -> Result<String, Error> {
+    let text = reader.read_string(len)?;
+
+    Ok(text.trim_end_matches(&[' ', '\0'][..]).to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(data: &mut [u8], offset: usize, text: &[u8]) {
+        data[offset..offset + text.len()].copy_from_slice(text);
+    }
+
+    #[test]
+    fn text_fields_and_timers_are_read() {
+        let mut data = vec![0; 2048];
+        field(&mut data, 0, b"0101");
+        field(&mut data, 4, b"Morning News   ");
+        field(&mut data, 132, b"CUT-0042");
+        field(&mut data, 680, &(-20i32).to_le_bytes());
+        field(&mut data, 684, b"SEC1");
+        field(&mut data, 688, &44100u32.to_le_bytes());
+        field(&mut data, 1024, b"https://example.com");
+
+        let cart = read_cart_chunk(&data, 0, Endianness::Little).unwrap();
+
+        assert_eq!(cart.version, "0101");
+        assert_eq!(cart.title, "Morning News");
+        assert_eq!(cart.cut_id, "CUT-0042");
+        assert_eq!(cart.artist, "");
+        assert_eq!(cart.level_reference, -20);
+        assert_eq!(cart.post_timers.len(), 1);
+        assert_eq!(cart.post_timers[0].usage.to_string(), "SEC1");
+        assert_eq!(cart.post_timers[0].value, 44100);
+        assert_eq!(cart.url, "https://example.com");
+        assert_eq!(cart.tag_text, "");
+    }
+
+    #[test]
+    fn short_cart_chunk_is_invalid() {
+        assert!(matches!(
+            read_cart_chunk(&[0; 100], 36, Endianness::Little),
+            Err(Error::Parse {
+                offset: 36,
+                kind: ParseKind::InvalidCartChunk
+            })
+        ));
+    }
+}
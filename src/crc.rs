@@ -0,0 +1,15 @@
+/// Continues the CRC-32 (IEEE) `crc` of the preceding bytes over `data`.
+/// Starting from 0 gives the checksum of `data` alone.
+pub(crate) fn crc32(crc: u32, data: &[u8]) -> u32 {
+    let mut crc = !crc;
+
+    for byte in data {
+        crc ^= *byte as u32;
+
+        for _ in 0..8 {
+            crc = (crc >> 1) ^ (0xedb8_8320 & (crc & 1).wrapping_neg());
+        }
+    }
+
+    !crc
+}
@@ -0,0 +1,110 @@
+use std::io::Cursor;
+
+use crate::{Endianness, Error, FourCC, ParseKind, ReadExt};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CuePoint {
+    pub id: u32,
+    pub position: u32,
+    pub data_chunk_id: FourCC,
+    pub chunk_start: u32,
+    pub block_start: u32,
+    pub sample_offset: u32,
+}
+
+pub(crate) fn read_cue_points(
+    data: &[u8],
+    offset: u64,
+    endianness: Endianness,
+) -> Result<Vec<CuePoint>, Error> {
+    if data.len() < 4 {
+        return Err(Error::parse(offset, ParseKind::InvalidCueChunk));
+    }
+
+    let mut reader = Cursor::new(data);
+
+    let num_cue_points = reader.read_u32(endianness)?;
+    if (data.len() as u64 - 4) / 24 < num_cue_points as u64 {
+        return Err(Error::parse(offset, ParseKind::InvalidCueChunk));
+    }
+
+    let mut cue_points = Vec::with_capacity(num_cue_points as usize);
+
+    for _ in 0..num_cue_points {
+        let id = reader.read_u32(endianness)?;
+        let position = reader.read_u32(endianness)?;
+        let data_chunk_id = reader.read_fourcc()?;
+        let chunk_start = reader.read_u32(endianness)?;
+        let block_start = reader.read_u32(endianness)?;
+        let sample_offset = reader.read_u32(endianness)?;
+
+        cue_points.push(CuePoint {
+            id,
+            position,
+            data_chunk_id,
+            chunk_start,
+            block_start,
+            sample_offset,
+        });
+    }
+
+    Ok(cue_points)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cue_point(id: u32, sample_offset: u32) -> Vec<u8> {
+        let mut data = vec![];
+        data.extend_from_slice(&id.to_le_bytes());
+        data.extend_from_slice(&sample_offset.to_le_bytes());
+        data.extend_from_slice(b"data");
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data.extend_from_slice(&sample_offset.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn cue_points_are_read() {
+        let mut data = 2u32.to_le_bytes().to_vec();
+        data.extend(cue_point(1, 100));
+        data.extend(cue_point(2, 4410));
+
+        let cue_points = read_cue_points(&data, 0, Endianness::Little).unwrap();
+
+        assert_eq!(cue_points.len(), 2);
+        assert_eq!(cue_points[0].id, 1);
+        assert_eq!(cue_points[0].data_chunk_id, FourCC::Data);
+        assert_eq!(cue_points[0].sample_offset, 100);
+        assert_eq!(cue_points[1].id, 2);
+        assert_eq!(cue_points[1].sample_offset, 4410);
+    }
+
+    #[test]
+    fn cue_point_count_exceeding_chunk_is_invalid() {
+        let mut data = 2u32.to_le_bytes().to_vec();
+        data.extend(cue_point(1, 100));
+
+        assert!(matches!(
+            read_cue_points(&data, 36, Endianness::Little),
+            Err(Error::Parse {
+                offset: 36,
+                kind: ParseKind::InvalidCueChunk
+            })
+        ));
+    }
+
+    #[test]
+    fn cue_chunk_shorter_than_its_count_is_invalid() {
+        assert!(matches!(
+            read_cue_points(&[1, 0], 36, Endianness::Little),
+            Err(Error::Parse {
+                offset: 36,
+                kind: ParseKind::InvalidCueChunk
+            })
+        ));
+    }
+}
@@ -1,22 +1,154 @@
 use std::io;
 use thiserror::Error;
 
+use crate::{Format, HeaderError, DEFAULT_MAX_CHUNK_SIZE};
+
 #[derive(Debug, Error)]
 pub enum Error {
-    #[error("Not a riff file")]
-    NotRiff,
-    #[error("Not a wave format file")]
-    NotWave,
-    #[error("Invalid fmt chunk")]
-    InvalidFmtChunk,
-    #[error("Invalid Extended Info, less than 22 bytes")]
-    InvalidExtendedInfo,
+    #[error("{kind} at offset {offset}")]
+    Parse { offset: u64, kind: ParseKind },
+    #[error("Inconsistent fmt chunk {field}, expected {expected} but found {found}")]
+    InconsistentFmt {
+        field: &'static str,
+        expected: u32,
+        found: u32,
+    },
+    #[error("Invalid channel count {num_channels} for a block align of {block_align}")]
+    InvalidChannelCount { num_channels: u16, block_align: u16 },
+    #[error("Data chunk declares {declared} bytes but only {available} are present")]
+    TruncatedData { declared: u64, available: u64 },
+    #[error("Riff chunk declares a file size of {declared} bytes but found {actual}")]
+    FileSizeMismatch { declared: u64, actual: u64 },
+    #[error("Invalid list chunk, sub-chunk exceeds list size")]
+    InvalidListChunk,
+    #[error("Unsupported format: {0:?}")]
+    UnsupportedFormat(Format),
+    #[error("Unsupported bit depth: {0}")]
+    UnsupportedBitDepth(u16),
+    #[error("Data ends with an incomplete sample")]
+    IncompleteSample,
+    #[error("Data ends with an incomplete frame")]
+    IncompleteFrame,
+    #[error("Seek position is past the end of the data chunk")]
+    SeekOutOfRange,
+    #[error("Sample loop {0} does not exist or exceeds the data chunk")]
+    InvalidLoop(usize),
+    #[error("Formats are not compatible")]
+    IncompatibleFormat,
+    #[error("Data size exceeds the riff size limit")]
+    DataTooLarge,
+    #[error("Chunk of {size} bytes exceeds the limit of {limit} bytes")]
+    ChunkTooLarge { size: u32, limit: u32 },
     #[error("IO error reading file: {0}")]
     IOError(io::Error),
 }
 
+/// A spec violation tolerated by
+/// [`RiffWaveReader::new_collect_errors`](crate::RiffWaveReader::new_collect_errors),
+/// holding the error a strict parse would have returned.
+#[derive(Debug, Error)]
+#[error(transparent)]
+pub struct ParseWarning(pub Error);
+
+impl Error {
+    pub(crate) fn parse(offset: u64, kind: ParseKind) -> Self {
+        Error::Parse { offset, kind }
+    }
+
+    /// Reports the stream ending early while reading `context` as a parse
+    /// error at `offset`, leaving other errors unchanged.
+    pub(crate) fn eof_context(self, offset: u64, context: &'static str) -> Self {
+        match self {
+            Error::IOError(e) if e.kind() == io::ErrorKind::UnexpectedEof => {
+                Error::parse(offset, ParseKind::UnexpectedEof { context })
+            }
+            e => e,
+        }
+    }
+
+    /// Converts an error from the [`Source`](crate::Source) parsers into a
+    /// parse error at `offset`, reporting the source ending early as the end
+    /// of `context`.
+    pub(crate) fn from_header(error: HeaderError, offset: u64, context: &'static str) -> Self {
+        let kind = match error {
+            HeaderError::UnexpectedEof => ParseKind::UnexpectedEof { context },
+            HeaderError::NotRiff => ParseKind::NotRiff,
+            HeaderError::NotWave => ParseKind::NotWave,
+            HeaderError::InvalidDs64Chunk => ParseKind::InvalidDs64Chunk,
+            HeaderError::InvalidFmtChunk => ParseKind::InvalidFmtChunk,
+            HeaderError::InvalidExtendedInfo => ParseKind::InvalidExtendedInfo,
+            HeaderError::InvalidFactChunk => ParseKind::InvalidFactChunk,
+            HeaderError::MissingDataChunk => ParseKind::MissingDataChunk,
+            HeaderError::MissingFmtChunk => ParseKind::MissingFmtChunk,
+            HeaderError::ChunkTooLarge { size } => {
+                return Error::ChunkTooLarge {
+                    size,
+                    limit: DEFAULT_MAX_CHUNK_SIZE,
+                }
+            }
+            HeaderError::Io(kind) => return Error::IOError(kind.into()),
+        };
+
+        Error::parse(offset, kind)
+    }
+
+    /// Splits an error from parsing a chunk held in memory into its offset
+    /// and kind. Reads from memory only fail by running out of data, which
+    /// is reported as the end of `context`.
+    pub(crate) fn into_parse(self, offset: u64, context: &'static str) -> (u64, ParseKind) {
+        match self {
+            Error::Parse { offset, kind } => (offset, kind),
+            Error::InvalidListChunk => (offset, ParseKind::InvalidListChunk),
+            _ => (offset, ParseKind::UnexpectedEof { context }),
+        }
+    }
+}
+
 impl From<io::Error> for Error {
     fn from(error: io::Error) -> Self {
         Error::IOError(error)
     }
 }
+
+/// The kind of failure encountered while parsing the headers of a file.
+#[derive(Debug, Clone, Error)]
+pub enum ParseKind {
+    #[error("Not a riff file")]
+    NotRiff,
+    #[error("Not a wave format file")]
+    NotWave,
+    #[error("Invalid bext chunk, less than 602 bytes")]
+    InvalidBextChunk,
+    #[error("Invalid cart chunk, less than 2048 bytes")]
+    InvalidCartChunk,
+    #[error("Invalid cue chunk, header or cue points exceed chunk size")]
+    InvalidCueChunk,
+    #[error("Invalid smpl chunk, header or sample loops exceed chunk size")]
+    InvalidSmplChunk,
+    #[error("Invalid PEAK chunk, peak entries do not match channel count")]
+    InvalidPeakChunk,
+    #[error("Invalid list chunk, sub-chunk exceeds list size")]
+    InvalidListChunk,
+    #[error("Invalid acid chunk, less than 24 bytes")]
+    InvalidAcidChunk,
+    #[error("Invalid ds64 chunk")]
+    InvalidDs64Chunk,
+    #[error("Invalid fmt chunk")]
+    InvalidFmtChunk,
+    #[error("Invalid Extended Info, less than 22 bytes or larger than the fmt chunk")]
+    InvalidExtendedInfo,
+    #[error("Invalid fact chunk, less than 4 bytes")]
+    InvalidFactChunk,
+    #[error("No data chunk found before end of file")]
+    MissingDataChunk,
+    #[error("No fmt chunk found before the data chunk")]
+    MissingFmtChunk,
+    #[error("Unexpected end of file reading {context}")]
+    UnexpectedEof { context: &'static str },
+    #[error("No fact chunk found for non-PCM data")]
+    MissingFactChunk,
+    #[error("Chunk does not start on an even offset")]
+    MisalignedChunk,
+    #[error("Skipped chunk of {size} bytes exceeding the size limit")]
+    SkippedChunk { size: u32 },
+}
@@ -0,0 +1,200 @@
+//! Builders for the in-memory files used by the tests.
+
+use std::io::Cursor;
+use std::path::PathBuf;
+
+use byteorder::{BigEndian, ByteOrder, LittleEndian};
+
+use crate::{Endianness, RiffWaveReader};
+
+/// Assembles a riff file from chunks, computing the riff size.
+pub(crate) struct WavBuilder {
+    riff_id: [u8; 4],
+    endianness: Endianness,
+    body: Vec<u8>,
+}
+
+impl WavBuilder {
+    pub fn new() -> WavBuilder {
+        WavBuilder::with_id(*b"RIFF")
+    }
+
+    pub fn rifx() -> WavBuilder {
+        WavBuilder {
+            endianness: Endianness::Big,
+            ..WavBuilder::with_id(*b"RIFX")
+        }
+    }
+
+    pub fn with_id(riff_id: [u8; 4]) -> WavBuilder {
+        WavBuilder {
+            riff_id,
+            endianness: Endianness::Little,
+            body: b"WAVE".to_vec(),
+        }
+    }
+
+    /// Appends a chunk with its pad byte.
+    pub fn chunk(self, id: &[u8; 4], data: &[u8]) -> WavBuilder {
+        let pad = if data.len() % 2 == 1 { &[0][..] } else { &[] };
+
+        self.chunk_sized(id, data.len() as u32, data).raw(pad)
+    }
+
+    /// Appends a chunk declaring `size` bytes, followed by `data` as is.
+    pub fn chunk_sized(self, id: &[u8; 4], size: u32, data: &[u8]) -> WavBuilder {
+        let size = u32_bytes(self.endianness, size);
+
+        self.raw(id).raw(&size).raw(data)
+    }
+
+    pub fn raw(mut self, bytes: &[u8]) -> WavBuilder {
+        self.body.extend_from_slice(bytes);
+        self
+    }
+
+    /// Appends a 16 byte PCM fmt chunk.
+    pub fn fmt(self, num_channels: u16, sample_rate: u32, bits_per_sample: u16) -> WavBuilder {
+        self.fmt_format(1, num_channels, sample_rate, bits_per_sample)
+    }
+
+    /// Appends a 16 byte fmt chunk with the given format tag.
+    pub fn fmt_format(
+        self,
+        format: u16,
+        num_channels: u16,
+        sample_rate: u32,
+        bits_per_sample: u16,
+    ) -> WavBuilder {
+        let body = fmt_body(
+            self.endianness,
+            format,
+            num_channels,
+            sample_rate,
+            bits_per_sample,
+        );
+
+        self.chunk(b"fmt ", &body)
+    }
+
+    /// Appends a 40 byte `WAVE_FORMAT_EXTENSIBLE` fmt chunk whose sub format
+    /// GUID holds the given format tag.
+    pub fn fmt_extensible(
+        self,
+        sub_format: u16,
+        num_channels: u16,
+        sample_rate: u32,
+        bits_per_sample: u16,
+        channel_mask: u32,
+    ) -> WavBuilder {
+        let endianness = self.endianness;
+
+        let mut body = fmt_body(
+            endianness,
+            0xfffe,
+            num_channels,
+            sample_rate,
+            bits_per_sample,
+        );
+        body.extend_from_slice(&u16_bytes(endianness, 22));
+        body.extend_from_slice(&u16_bytes(endianness, bits_per_sample));
+        body.extend_from_slice(&u32_bytes(endianness, channel_mask));
+        body.extend_from_slice(&u32_bytes(endianness, sub_format as u32));
+        body.extend_from_slice(&u16_bytes(endianness, 0x0000));
+        body.extend_from_slice(&u16_bytes(endianness, 0x0010));
+        body.extend_from_slice(&[0x80, 0x00, 0x00, 0xaa, 0x00, 0x38, 0x9b, 0x71]);
+
+        self.chunk(b"fmt ", &body)
+    }
+
+    pub fn data(self, data: &[u8]) -> WavBuilder {
+        self.chunk(b"data", data)
+    }
+
+    pub fn build(self) -> Vec<u8> {
+        let riff_size = self.body.len() as u32;
+
+        let mut bytes = self.riff_id.to_vec();
+        bytes.extend_from_slice(&u32_bytes(self.endianness, riff_size));
+        bytes.extend_from_slice(&self.body);
+        bytes
+    }
+
+    pub fn reader(self) -> RiffWaveReader<Cursor<Vec<u8>>> {
+        RiffWaveReader::from_bytes(self.build()).unwrap()
+    }
+}
+
+/// Returns the 16 byte body of a fmt chunk, deriving `block_align` and
+/// `byte_rate`.
+pub(crate) fn fmt_body(
+    endianness: Endianness,
+    format: u16,
+    num_channels: u16,
+    sample_rate: u32,
+    bits_per_sample: u16,
+) -> Vec<u8> {
+    let block_align = num_channels * bits_per_sample.div_ceil(8);
+
+    let mut body = vec![];
+    body.extend_from_slice(&u16_bytes(endianness, format));
+    body.extend_from_slice(&u16_bytes(endianness, num_channels));
+    body.extend_from_slice(&u32_bytes(endianness, sample_rate));
+    body.extend_from_slice(&u32_bytes(endianness, sample_rate * block_align as u32));
+    body.extend_from_slice(&u16_bytes(endianness, block_align));
+    body.extend_from_slice(&u16_bytes(endianness, bits_per_sample));
+    body
+}
+
+/// Returns the body of a `LIST` chunk of `list_type` holding the given
+/// sub-chunks, each padded to an even size.
+pub(crate) fn list_data(
+    endianness: Endianness,
+    list_type: &[u8; 4],
+    sub_chunks: &[(&[u8; 4], &[u8])],
+) -> Vec<u8> {
+    let mut data = list_type.to_vec();
+
+    for (id, body) in sub_chunks {
+        data.extend_from_slice(*id);
+        data.extend_from_slice(&u32_bytes(endianness, body.len() as u32));
+        data.extend_from_slice(body);
+
+        if body.len() % 2 == 1 {
+            data.push(0);
+        }
+    }
+
+    data
+}
+
+pub(crate) fn u16_bytes(endianness: Endianness, n: u16) -> [u8; 2] {
+    let mut buf = [0; 2];
+    match endianness {
+        Endianness::Little => LittleEndian::write_u16(&mut buf, n),
+        Endianness::Big => BigEndian::write_u16(&mut buf, n),
+    }
+    buf
+}
+
+pub(crate) fn u32_bytes(endianness: Endianness, n: u32) -> [u8; 4] {
+    let mut buf = [0; 4];
+    match endianness {
+        Endianness::Little => LittleEndian::write_u32(&mut buf, n),
+        Endianness::Big => BigEndian::write_u32(&mut buf, n),
+    }
+    buf
+}
+
+/// Encodes 16-bit samples in the given byte order.
+pub(crate) fn pcm16(endianness: Endianness, samples: &[i16]) -> Vec<u8> {
+    samples
+        .iter()
+        .flat_map(|sample| u16_bytes(endianness, *sample as u16))
+        .collect()
+}
+
+/// Returns a path in the temp directory unique to this process and `name`.
+pub(crate) fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("riff-wave-reader-{}-{}", std::process::id(), name))
+}
@@ -0,0 +1,62 @@
+/// Expands an 8-bit G.711 A-law sample to linear 16-bit PCM.
+pub(crate) fn alaw_to_linear(a_val: u8) -> i16 {
+    let a_val = a_val ^ 0x55;
+
+    let mut t = ((a_val & 0x0f) as i16) << 4;
+    let seg = (a_val & 0x70) >> 4;
+
+    match seg {
+        0 => t += 8,
+        1 => t += 0x108,
+        _ => {
+            t += 0x108;
+            t <<= seg - 1;
+        }
+    }
+
+    if a_val & 0x80 != 0 {
+        t
+    } else {
+        -t
+    }
+}
+
+/// Expands an 8-bit G.711 mu-law sample to linear 16-bit PCM.
+pub(crate) fn ulaw_to_linear(u_val: u8) -> i16 {
+    let u_val = !u_val;
+
+    let t = ((((u_val & 0x0f) as i16) << 3) + 0x84) << ((u_val & 0x70) >> 4);
+
+    if u_val & 0x80 != 0 {
+        0x84 - t
+    } else {
+        t - 0x84
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn alaw_matches_reference_values() {
+        assert_eq!(alaw_to_linear(0x00), -5504);
+        assert_eq!(alaw_to_linear(0x80), 5504);
+        assert_eq!(alaw_to_linear(0xff), 848);
+        assert_eq!(alaw_to_linear(0x7f), -848);
+        assert_eq!(alaw_to_linear(0xd5), 8);
+        assert_eq!(alaw_to_linear(0x55), -8);
+        assert_eq!(alaw_to_linear(0xaa), 32256);
+        assert_eq!(alaw_to_linear(0x2a), -32256);
+    }
+
+    #[test]
+    fn ulaw_matches_reference_values() {
+        assert_eq!(ulaw_to_linear(0x00), -32124);
+        assert_eq!(ulaw_to_linear(0x80), 32124);
+        assert_eq!(ulaw_to_linear(0xff), 0);
+        assert_eq!(ulaw_to_linear(0x7f), 0);
+        assert_eq!(ulaw_to_linear(0xfe), 8);
+        assert_eq!(ulaw_to_linear(0x7e), -8);
+    }
+}
@@ -0,0 +1,101 @@
+use std::io::Cursor;
+
+use crate::{Endianness, Error, FourCC, ReadExt};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InfoTag {
+    pub id: FourCC,
+    pub value: String,
+}
+
+pub(crate) fn read_info_tags(
+    list_data: &[u8],
+    endianness: Endianness,
+) -> Result<Option<Vec<InfoTag>>, Error> {
+    let mut reader = Cursor::new(list_data);
+
+    match reader.read_fourcc_or_eof()? {
+        Some(FourCC::Other(list_type)) if list_type == "INFO" => {}
+        _ => return Ok(None),
+    }
+
+    let mut tags = vec![];
+
+    while let Some(id) = reader.read_fourcc_or_eof()? {
+        let data_size = reader.read_u32(endianness)?;
+        if data_size as u64 > list_data.len() as u64 - reader.position() {
+            return Err(Error::InvalidListChunk);
+        }
+
+        let value = reader.read_string(data_size as usize)?;
+
+        if data_size % 2 == 1 {
+            reader.set_position(reader.position() + 1);
+        }
+
+        tags.push(InfoTag { id, value });
+    }
+
+    Ok(Some(tags))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture::{list_data, WavBuilder};
+
+    #[test]
+    fn info_list_is_read_into_tags() {
+        let data = list_data(
+            Endianness::Little,
+            b"INFO",
+            &[(b"INAM", b"Title\0"), (b"IART", b"Artist\0")],
+        );
+
+        let tags = read_info_tags(&data, Endianness::Little).unwrap().unwrap();
+
+        assert_eq!(tags.len(), 2);
+        assert_eq!(tags[0].id, FourCC::Other(String::from("INAM")));
+        assert_eq!(tags[0].value, "Title");
+        assert_eq!(tags[1].id, FourCC::Other(String::from("IART")));
+        assert_eq!(tags[1].value, "Artist");
+    }
+
+    #[test]
+    fn odd_sized_tags_skip_their_pad_byte() {
+        let data = list_data(
+            Endianness::Little,
+            b"INFO",
+            &[(b"ICMT", b"odd\0\0"), (b"ICRD", b"2020\0")],
+        );
+
+        let tags = read_info_tags(&data, Endianness::Little).unwrap().unwrap();
+
+        assert_eq!(tags[0].value, "odd");
+        assert_eq!(tags[1].id, FourCC::Other(String::from("ICRD")));
+        assert_eq!(tags[1].value, "2020");
+    }
+
+    #[test]
+    fn other_list_types_are_not_info() {
+        let data = list_data(Endianness::Little, b"adtl", &[]);
+
+        assert!(read_info_tags(&data, Endianness::Little).unwrap().is_none());
+    }
+
+    #[test]
+    fn reader_returns_info_tags() {
+        let data = list_data(Endianness::Little, b"INFO", &[(b"INAM", b"Title\0")]);
+        let reader = WavBuilder::new()
+            .fmt(1, 8000, 8)
+            .chunk(b"LIST", &data)
+            .data(&[0])
+            .reader();
+
+        let tags = reader.info_tags().unwrap().unwrap();
+
+        assert_eq!(tags.len(), 1);
+        assert_eq!(tags[0].value, "Title");
+    }
+}
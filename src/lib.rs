@@ -1,465 +1,4582 @@
-use byteorder::{ByteOrder, LittleEndian};
+#![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::array::TryFromSliceError;
+use core::convert::TryFrom;
+
+#[cfg(feature = "std")]
+use byteorder::{BigEndian, ByteOrder, LittleEndian};
+
+#[cfg(feature = "std")]
+use std::fs::File;
+#[cfg(feature = "std")]
+use std::io;
+#[cfg(feature = "std")]
+use std::io::BufReader;
+#[cfg(feature = "std")]
+use std::io::Cursor;
+#[cfg(feature = "std")]
 use std::io::Read;
+#[cfg(feature = "std")]
 use std::io::Seek;
+#[cfg(feature = "std")]
 use std::io::SeekFrom;
+#[cfg(feature = "std")]
+use std::path::Path;
+#[cfg(feature = "std")]
+use std::time::Duration;
+
+#[cfg(feature = "std")]
+mod acid;
+#[cfg(feature = "std")]
+pub use acid::AcidChunk;
+
+#[cfg(feature = "std")]
+mod adtl;
+#[cfg(feature = "std")]
+pub use adtl::{AdtlEntry, AdtlKind};
+
+#[cfg(feature = "async")]
+mod async_reader;
+#[cfg(feature = "async")]
+pub use async_reader::AsyncRiffWaveReader;
+
+#[cfg(feature = "std")]
+mod bext;
+#[cfg(feature = "std")]
+pub use bext::BextChunk;
+
+#[cfg(feature = "std")]
+mod cart;
+#[cfg(feature = "std")]
+pub use cart::{CartChunk, CartTimer};
 
+mod crc;
+
+#[cfg(feature = "std")]
+mod cue;
+#[cfg(feature = "std")]
+pub use cue::CuePoint;
+
+#[cfg(feature = "std")]
 mod error;
-pub use error::Error;
+#[cfg(feature = "std")]
+pub use error::{Error, ParseKind, ParseWarning};
+
+#[cfg(all(test, feature = "std"))]
+mod fixture;
+
+#[cfg(feature = "std")]
+mod g711;
+
+#[cfg(feature = "std")]
+mod info;
+#[cfg(feature = "std")]
+pub use info::InfoTag;
+
+#[cfg(feature = "std")]
+mod peak;
+#[cfg(feature = "std")]
+pub use peak::{PeakChunk, PeakPosition};
+
+#[cfg(feature = "serde")]
+mod serde_impl;
+
+#[cfg(feature = "std")]
+mod smpl;
+#[cfg(feature = "std")]
+pub use smpl::{SampleLoop, SmplChunk};
 
-#[derive(Debug)]
+mod source;
+#[cfg(feature = "std")]
+pub use source::ReadSource;
+#[cfg(feature = "std")]
+use source::SourceExt;
+pub use source::{read_header, HeaderError, SliceSource, Source, WaveHeader};
+
+mod speaker;
+pub use speaker::SpeakerPosition;
+
+#[cfg(feature = "std")]
+mod wavl;
+#[cfg(feature = "std")]
+pub use wavl::WaveSegment;
+
+#[cfg(feature = "std")]
+mod writer;
+#[cfg(feature = "std")]
+pub use writer::{concat, write_pcm, RiffWaveWriter};
+
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
 pub struct RiffWaveReader<T: Read + Seek> {
     reader: T,
     pub riff_chunk: RiffChunk,
     pub fmt_chunk: FmtChunk,
     pub fact_chunk: Option<FactChunk>,
+    pub ds64_chunk: Option<Ds64Chunk>,
     pub data_chunk: DataChunk,
     pub other_chunks: Vec<OtherChunk>,
+    pub bext: Option<BextChunk>,
+    pub cart: Option<CartChunk>,
+    pub cue_points: Option<Vec<CuePoint>>,
+    pub adtl: Option<Vec<AdtlEntry>>,
+    pub smpl: Option<SmplChunk>,
+    pub peak: Option<PeakChunk>,
+    pub ixml: Option<String>,
+    pub acid: Option<AcidChunk>,
+    /// The raw ID3v2 tag of an `id3 ` or `ID3 ` chunk.
+    pub id3: Option<Vec<u8>>,
+    /// The segments of a `wavl` list. Files storing their waveform this way
+    /// have no data chunk, so `data_chunk` is empty.
+    pub wave_segments: Option<Vec<WaveSegment>>,
+    pub endianness: Endianness,
+    riff_offset: u64,
+    data_offset: u64,
+    chunk_layout: Vec<ChunkInfo>,
+    skipped_chunks: Vec<ChunkInfo>,
+    metadata_errors: Vec<(u64, ParseKind)>,
+    has_fmt_chunk: bool,
+    available_data: u64,
+    options: ParseOptions,
 }
 
+#[cfg(feature = "std")]
 impl<T: Read + Seek> RiffWaveReader<T> {
-    pub fn new(mut reader: T) -> Result<RiffWaveReader<T>, Error> {
-        let riff_chunk = reader.read_riff_chunk()?;
+    pub fn new(reader: T) -> Result<RiffWaveReader<T>, Error> {
+        RiffWaveReader::with_options(reader, ParseOptions::default())
+    }
 
-        if riff_chunk.id != FourCC::Riff {
-            return Err(Error::NotRiff);
-        }
+    /// Parses the file, rejecting any spec violation. See
+    /// [`ParseOptions::strict`].
+    pub fn new_strict(reader: T) -> Result<RiffWaveReader<T>, Error> {
+        RiffWaveReader::with_options(
+            reader,
+            ParseOptions {
+                strict: true,
+                ..ParseOptions::default()
+            },
+        )
+    }
+
+    pub fn with_options(mut reader: T, options: ParseOptions) -> Result<RiffWaveReader<T>, Error> {
+        let headers = read_headers(&mut reader, &options, true)?;
+        let mut data_chunk = headers.data_chunk.unwrap();
+
+        let data_offset = reader.stream_position()?;
+
+        let stream_end = match options.stream_length {
+            Some(length) => length,
+            None => reader.seek(SeekFrom::End(0))?,
+        };
+
+        // The end of this file, which may be followed by another in the stream
+        let riff_end = match riff_size(&headers.riff_chunk, headers.ds64_chunk.as_ref()) {
+            0 | 0xffff_ffff => stream_end,
+            size => (headers.riff_offset + 8)
+                .saturating_add(size)
+                .min(stream_end),
+        };
+        let riff_end = if riff_end > data_offset {
+            riff_end
+        } else {
+            stream_end
+        };
 
-        if riff_chunk.file_type != FourCC::Wave {
-            return Err(Error::NotWave);
+        if data_chunk.is_unsized
+            && data_chunk.data_size == 0
+            && is_chunk_header_at(&mut reader, data_offset, stream_end, headers.endianness)?
+        {
+            data_chunk.is_unsized = false;
         }
 
-        let fmt_chunk = reader.read_fmt_chunk()?;
+        if data_chunk.is_unsized {
+            data_chunk.data_size = riff_end - data_offset;
+            data_chunk.pad_byte = 0;
+        }
 
-        let fact_chunk = reader.read_fact_chunk()?;
+        let available_data = stream_end
+            .saturating_sub(data_offset)
+            .min(data_chunk.data_size);
 
-        let mut other_chunks = vec![];
-        reader.read_other_chunks(&mut other_chunks)?;
+        let mut chunk_layout = headers.chunk_layout;
 
-        let data_chunk = reader.read_data_chunk()?;
+        let data_end = data_end(data_offset, &data_chunk);
+        chunk_layout.extend(read_trailing_chunks(
+            &mut reader,
+            data_end,
+            riff_end,
+            headers.endianness,
+        ));
+        reader.seek(SeekFrom::Start(data_offset))?;
 
-        let riff_reader = RiffWaveReader {
+        let mut riff_reader = RiffWaveReader {
             reader,
-            riff_chunk,
-            fmt_chunk,
-            fact_chunk,
+            riff_chunk: headers.riff_chunk,
+            fmt_chunk: headers.fmt_chunk,
+            fact_chunk: headers.fact_chunk,
+            ds64_chunk: headers.ds64_chunk,
             data_chunk,
-            other_chunks,
+            other_chunks: headers.other_chunks,
+            bext: headers.bext,
+            cart: headers.cart,
+            cue_points: headers.cue_points,
+            adtl: headers.adtl,
+            smpl: headers.smpl,
+            peak: headers.peak,
+            ixml: headers.ixml,
+            acid: headers.acid,
+            id3: headers.id3,
+            wave_segments: headers.wave_segments,
+            endianness: headers.endianness,
+            riff_offset: headers.riff_offset,
+            data_offset,
+            chunk_layout,
+            skipped_chunks: headers.skipped_chunks,
+            metadata_errors: headers.metadata_errors,
+            has_fmt_chunk: headers.has_fmt_chunk,
+            available_data,
+            options,
         };
 
+        if options.strict {
+            riff_reader.validate_strict()?;
+        }
+
         Ok(riff_reader)
     }
 
+    /// Parses the file leniently, recovering from missing pad bytes and
+    /// skipping oversized chunks, and returns as warnings the spec violations
+    /// that a strict parse would reject, the skipped chunks and the metadata
+    /// chunks that failed to parse. Only files that can't be read at all
+    /// fail.
+    pub fn new_collect_errors(reader: T) -> Result<(RiffWaveReader<T>, Vec<ParseWarning>), Error> {
+        let mut riff_reader = RiffWaveReader::with_options(
+            reader,
+            ParseOptions {
+                recover_missing_padding: true,
+                skip_oversized_chunks: true,
+                ..ParseOptions::default()
+            },
+        )?;
+
+        let warnings = riff_reader
+            .spec_violations()?
+            .into_iter()
+            .map(ParseWarning)
+            .collect();
+
+        Ok((riff_reader, warnings))
+    }
+
+    /// Parses the file from a stream of a known total length. The length takes
+    /// precedence over the end of the stream when the data chunk is unsized.
+    /// See [`ParseOptions::stream_length`].
+    pub fn with_length(reader: T, length: u64) -> Result<RiffWaveReader<T>, Error> {
+        RiffWaveReader::with_options(
+            reader,
+            ParseOptions {
+                stream_length: Some(length),
+                ..ParseOptions::default()
+            },
+        )
+    }
+
+    /// Parses the chunks up to the data chunk without requiring one, for
+    /// header-only files or streams whose data isn't available yet.
+    pub fn new_headers_only(mut reader: T) -> Result<RiffWaveHeaders, Error> {
+        read_headers(&mut reader, &ParseOptions::default(), false)
+    }
+
     pub fn data(&mut self) -> Result<impl Iterator<Item = u8>, Error> {
-        let mut data = vec![];
-        self.reader.read_to_end(&mut data)?;
+        let data = self.read_data()?;
 
         Ok(data.into_iter())
     }
 
-    pub fn print_info(&self) {
-        println!("{}", self);
+    pub fn data_offset(&self) -> u64 {
+        self.data_offset
     }
 
-    pub fn into_reader(self) -> T {
-        self.reader
+    /// Returns the current offset of the underlying reader. Subtract
+    /// [`data_offset`](Self::data_offset) for the progress into the data.
+    pub fn position(&mut self) -> Result<u64, Error> {
+        Ok(self.reader.stream_position()?)
     }
-}
 
-trait ReadExt: Read + Seek {
-    fn read_riff_chunk(&mut self) -> Result<RiffChunk, Error>;
+    /// Positions the reader at the first byte following the data chunk,
+    /// including its pad byte.
+    pub fn skip_to_after_data(&mut self) -> Result<(), Error> {
+        self.reader.seek(SeekFrom::Start(self.data_end()))?;
+
+        Ok(())
+    }
+
+    /// Returns false if the file had no fmt chunk and was parsed with
+    /// [`ParseOptions::recover_missing_fmt`], until one is set.
+    pub fn has_fmt_chunk(&self) -> bool {
+        self.has_fmt_chunk
+    }
 
-    fn read_fmt_chunk(&mut self) -> Result<FmtChunk, Error>;
+    /// Supplies the fmt chunk of a file recovered without one.
+    pub fn set_fmt_chunk(&mut self, fmt_chunk: FmtChunk) {
+        self.fmt_chunk = fmt_chunk;
+        self.has_fmt_chunk = true;
+    }
 
-    fn read_extended_info(&mut self, size: u16) -> Result<Option<ExtendedInfo>, Error>;
+    /// Returns true if the stream ends before the declared size of the data
+    /// chunk, as in aborted recordings. Reads stop at the end of the stream.
+    pub fn is_truncated(&self) -> bool {
+        self.available_data < self.data_chunk.data_size
+    }
 
-    fn read_fact_chunk(&mut self) -> Result<Option<FactChunk>, Error>;
+    /// Returns the number of data bytes present in the stream, which is less
+    /// than the declared `data_size` for truncated files.
+    pub fn available_data_size(&self) -> u64 {
+        self.available_data
+    }
 
-    fn read_other_chunks(&mut self, other_chunks: &mut Vec<OtherChunk>) -> Result<(), Error>;
+    /// Returns the ids of the recognized chunks that were found before the
+    /// data, with the data chunk last unless the waveform is held in a `wavl`
+    /// list.
+    pub fn present_chunks(&self) -> Vec<FourCC> {
+        let mut chunks = vec![];
 
-    fn read_data_chunk(&mut self) -> Result<DataChunk, Error>;
+        if self.ds64_chunk.is_some() {
+            chunks.push(FourCC::Ds64);
+        }
 
-    fn read_fourcc(&mut self) -> Result<FourCC, Error>;
+        if self.has_fmt_chunk {
+            chunks.push(FourCC::Fmt);
+        }
 
-    fn read_u32(&mut self) -> Result<u32, Error>;
+        if self.fact_chunk.is_some() {
+            chunks.push(FourCC::Fact);
+        }
 
-    fn read_u16(&mut self) -> Result<u16, Error>;
+        let metadata = [
+            ("bext", self.bext.is_some()),
+            ("cart", self.cart.is_some()),
+            ("cue ", self.cue_points.is_some()),
+            ("LIST", self.get_chunk("LIST").is_some()),
+            ("smpl", self.smpl.is_some()),
+            ("PEAK", self.peak.is_some()),
+            ("iXML", self.ixml.is_some()),
+            ("acid", self.acid.is_some()),
+        ];
 
-    fn read_u128(&mut self) -> Result<u128, Error>;
+        chunks.extend(
+            metadata
+                .iter()
+                .filter(|(_, present)| *present)
+                .map(|(id, _)| FourCC::Other(String::from(*id))),
+        );
 
-    fn read_is_fourcc(&mut self) -> Result<bool, Error>;
-}
+        if self.id3.is_some() {
+            chunks.extend(
+                self.get_chunk("id3 ")
+                    .or_else(|| self.get_chunk("ID3 "))
+                    .map(|chunk| chunk.id.clone()),
+            );
+        }
 
-impl<T: Read + Seek> ReadExt for T {
-    fn read_riff_chunk(&mut self) -> Result<RiffChunk, Error> {
-        let id = self.read_fourcc()?;
-        let file_size = self.read_u32()?;
-        let file_type = self.read_fourcc()?;
+        if self.wave_segments.is_none() {
+            chunks.push(FourCC::Data);
+        }
 
-        Ok(RiffChunk {
-            id,
-            file_size,
-            file_type,
-        })
+        chunks
+    }
+
+    /// Returns every chunk in the file in on-disk order, including the chunks
+    /// following the data chunk.
+    pub fn chunk_layout(&self) -> Vec<ChunkInfo> {
+        self.chunk_layout.clone()
     }
 
-    fn read_fmt_chunk(&mut self) -> Result<FmtChunk, Error> {
-        let id = self.read_fourcc()?;
-        if id != FourCC::Fmt {
-            return Err(Error::InvalidFmtChunk);
+    /// Returns one line per chunk with its offset, size, raw id bytes and the
+    /// first 16 bytes of its data in hex, for diagnosing corrupt files. The
+    /// reader position is restored afterwards.
+    pub fn hex_dump_headers(&mut self) -> Result<String, Error> {
+        let position = self.reader.stream_position()?;
+
+        let mut dump = String::new();
+
+        for chunk in &self.chunk_layout {
+            self.reader.seek(SeekFrom::Start(chunk.offset + 8))?;
+
+            let mut data = vec![];
+            (&mut self.reader)
+                .take(chunk.size.min(16) as u64)
+                .read_to_end(&mut data)?;
+
+            dump.push_str(&format!(
+                "{:<4} offset {:>10} size {:>10} id {} data {}\n",
+                chunk.id,
+                chunk.offset,
+                chunk.size,
+                hex(&chunk.raw_id),
+                hex(&data)
+            ));
         }
 
-        let data_size = self.read_u32()?;
-        let format = Format::from(self.read_u16()?);
-        let num_channels = self.read_u16()?;
-        let sample_rate = self.read_u32()?;
-        let byte_rate = self.read_u32()?;
-        let block_align = self.read_u16()?;
-        let bits_per_raw_sample = self.read_u16()?;
+        self.reader.seek(SeekFrom::Start(position))?;
 
-        let (extra_info_size, extended_info) = if self.read_is_fourcc()? {
-            (0, None)
-        } else {
-            let extra_info_size = self.read_u16()?;
-            (extra_info_size, self.read_extended_info(extra_info_size)?)
-        };
+        Ok(dump)
+    }
 
-        Ok(FmtChunk {
-            id,
-            data_size,
-            format,
-            num_channels,
-            sample_rate,
-            byte_rate,
-            block_align,
-            bits_per_raw_sample,
-            extra_info_size,
-            extended_info,
-        })
+    pub fn data_reader(&mut self) -> Result<impl Read + '_, Error> {
+        self.reader.seek(SeekFrom::Start(self.data_offset))?;
+
+        Ok((&mut self.reader).take(self.data_chunk.data_size))
     }
 
-    fn read_extended_info(&mut self, size: u16) -> Result<Option<ExtendedInfo>, Error> {
-        if size == 0 {
-            return Ok(None);
-        }
+    /// Computes the CRC-32 of the data block in fixed size reads, leaving the
+    /// reader at the end of the data.
+    pub fn data_crc32(&mut self) -> Result<u32, Error> {
+        let mut crc = 0;
+        let mut buf = [0; 8192];
+        let mut data = self.data_reader()?;
+
+        loop {
+            let len = data.read(&mut buf)?;
+            if len == 0 {
+                break;
+            }
 
-        if size < 22 {
-            return Err(Error::InvalidExtendedInfo);
+            crc = crc::crc32(crc, &buf[..len]);
         }
 
-        let bits_per_coded_sample = self.read_u16()?;
-        let channel_mask = self.read_u32()?;
-        let sub_format = self.read_u128()?;
+        Ok(crc)
+    }
 
-        let remaining_size = (size - 22) as usize;
-        let mut remaining_data = vec![0; remaining_size];
-        self.read_exact(&mut remaining_data[..])?;
+    /// Positions the reader back at the start of the data block, so the
+    /// samples can be read again.
+    pub fn rewind_data(&mut self) -> Result<(), Error> {
+        self.reader.seek(SeekFrom::Start(self.data_offset))?;
 
-        Ok(Some(ExtendedInfo {
-            bits_per_coded_sample,
-            channel_mask,
-            sub_format,
-            remaining_data,
-        }))
+        Ok(())
     }
 
-    fn read_fact_chunk(&mut self) -> Result<Option<FactChunk>, Error> {
-        let id = self.read_fourcc()?;
-        if id != FourCC::Fact {
-            self.seek(SeekFrom::Current(-4))?;
-            return Ok(None);
+    pub fn seek_to_frame(&mut self, frame: u64) -> Result<(), Error> {
+        if frame > self.num_frames() {
+            return Err(Error::SeekOutOfRange);
         }
 
-        let data_size = self.read_u32()?;
-        let sample_length = self.read_u32()?;
+        let offset = frame
+            .checked_mul(self.fmt_chunk.block_align as u64)
+            .and_then(|offset| offset.checked_add(self.data_offset))
+            .ok_or(Error::SeekOutOfRange)?;
+        self.reader.seek(SeekFrom::Start(offset))?;
 
-        let remaining_size = (data_size - 4) as usize;
-        let mut remaining_data = vec![0; remaining_size];
-        self.read_exact(&mut remaining_data[..])?;
+        Ok(())
+    }
 
-        Ok(Some(FactChunk {
-            id,
-            data_size,
-            sample_length,
-            remaining_data,
-        }))
+    pub fn samples_i16(&mut self) -> Result<impl Iterator<Item = i16>, Error> {
+        let data = self.read_samples(Format::UncompressedPCM, 16)?;
+        let endianness = self.endianness;
+
+        Ok(data
+            .chunks_exact(2)
+            .map(|sample| endianness.read_i16(sample))
+            .collect::<Vec<_>>()
+            .into_iter())
     }
 
-    fn read_other_chunks(&mut self, other_chunks: &mut Vec<OtherChunk>) -> Result<(), Error> {
-        loop {
-            let fourcc = self.read_fourcc()?;
+    /// Returns the 16-bit samples paired with the index of the frame they
+    /// belong to.
+    pub fn indexed_samples_i16(&mut self) -> Result<impl Iterator<Item = (u64, i16)>, Error> {
+        let num_channels = self.fmt_chunk.num_channels.max(1) as u64;
 
-            if fourcc == FourCC::Data {
-                self.seek(SeekFrom::Current(-4))?;
-                return Ok(());
-            }
+        Ok(self
+            .samples_i16()?
+            .enumerate()
+            .map(move |(index, sample)| (index as u64 / num_channels, sample)))
+    }
 
-            let data_size = self.read_u32()?;
-            let mut data = vec![0; data_size as usize];
-            self.read_exact(&mut data)?;
+    pub fn samples_i24(&mut self) -> Result<impl Iterator<Item = i32>, Error> {
+        let data = self.read_samples(Format::UncompressedPCM, 24)?;
 
-            let chunk = OtherChunk {
-                id: fourcc,
-                data_size,
-                data,
-            };
+        let mut reader = Cursor::new(&data);
+        let samples = (0..data.len() / 3)
+            .map(|_| reader.read_u24(self.endianness).map(sign_extend_i24))
+            .collect::<Result<Vec<_>, _>>()?;
 
-            other_chunks.push(chunk);
-        }
+        Ok(samples.into_iter())
     }
 
-    fn read_data_chunk(&mut self) -> Result<DataChunk, Error> {
-        let id = self.read_fourcc()?;
-        let data_size = self.read_u32()?;
-
-        let pad_byte = if data_size % 2 == 0 { 0 } else { 1 };
+    /// Returns the samples as linear 16-bit PCM, expanding G.711 A-law and
+    /// mu-law data. 16-bit PCM data is passed through unchanged.
+    pub fn samples_decoded_i16(&mut self) -> Result<impl Iterator<Item = i16>, Error> {
+        let samples = match self.fmt_chunk.effective_format() {
+            Format::G711ALaw => self
+                .read_samples(Format::G711ALaw, 8)?
+                .into_iter()
+                .map(g711::alaw_to_linear)
+                .collect::<Vec<_>>(),
+            Format::G711ULaw => self
+                .read_samples(Format::G711ULaw, 8)?
+                .into_iter()
+                .map(g711::ulaw_to_linear)
+                .collect::<Vec<_>>(),
+            _ => self.samples_i16()?.collect::<Vec<_>>(),
+        };
 
-        Ok(DataChunk {
-            id,
-            data_size,
-            pad_byte,
-        })
+        Ok(samples.into_iter())
     }
 
-    fn read_fourcc(&mut self) -> Result<FourCC, Error> {
-        let mut buf = [0; 4];
+    /// Fills `buf` with 16-bit samples from the current position, returning
+    /// the number of samples read. Only whole samples are read and never past
+    /// the end of the data chunk, so 0 is returned once the data is exhausted.
+    pub fn read_frames(&mut self, buf: &mut [i16]) -> Result<usize, Error> {
+        self.check_format(Format::UncompressedPCM, 16)?;
 
-        self.read_exact(&mut buf)?;
+        let remaining = self.remaining_data()? / 2;
+        let len = (buf.len() as u64).min(remaining) as usize;
 
-        Ok(FourCC::from(&buf[..]))
-    }
+        let mut bytes = [0; 512];
 
-    fn read_u32(&mut self) -> Result<u32, Error> {
-        let mut buf = [0; 4];
+        for samples in buf[..len].chunks_mut(bytes.len() / 2) {
+            let bytes = &mut bytes[..samples.len() * 2];
 
-        self.read_exact(&mut buf)?;
+            self.reader.read_exact(bytes)?;
+            self.endianness.read_i16_into(bytes, samples);
+        }
 
-        Ok(LittleEndian::read_u32(&buf))
+        Ok(len)
     }
 
-    fn read_u16(&mut self) -> Result<u16, Error> {
-        let mut buf = [0; 2];
+    pub fn frames_i16(&mut self) -> Result<impl Iterator<Item = Vec<i16>>, Error> {
+        let data = self.read_samples(Format::UncompressedPCM, 16)?;
 
-        self.read_exact(&mut buf)?;
+        let block_align = self.fmt_chunk.block_align as usize;
+        if block_align == 0 || data.len() % block_align != 0 {
+            return Err(Error::IncompleteFrame);
+        }
 
-        Ok(LittleEndian::read_u16(&buf))
+        let endianness = self.endianness;
+
+        Ok(data
+            .chunks_exact(block_align)
+            .map(|frame| {
+                frame
+                    .chunks_exact(2)
+                    .map(|sample| endianness.read_i16(sample))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>()
+            .into_iter())
     }
 
-    fn read_u128(&mut self) -> Result<u128, Error> {
-        let mut buf = [0; 16];
+    /// Returns the 16-bit samples from the start of the data, with the frames
+    /// of the sample loop at `loop_index` of the smpl chunk played `repeats`
+    /// times before the tail. The loop end is inclusive, as in the smpl chunk.
+    pub fn looped_samples_i16(
+        &mut self,
+        loop_index: usize,
+        repeats: u32,
+    ) -> Result<impl Iterator<Item = i16>, Error> {
+        let (start, end) = self
+            .smpl
+            .as_ref()
+            .and_then(|smpl| smpl.sample_loops.get(loop_index))
+            .map(|sample_loop| (sample_loop.start as usize, sample_loop.end as usize))
+            .ok_or(Error::InvalidLoop(loop_index))?;
 
-        self.read_exact(&mut buf)?;
+        self.rewind_data()?;
+        let frames = self.frames_i16()?.collect::<Vec<_>>();
+
+        if start > end || end >= frames.len() {
+            return Err(Error::InvalidLoop(loop_index));
+        }
 
-        Ok(LittleEndian::read_u128(&buf))
+        let (intro, rest) = frames.split_at(start);
+        let (loop_frames, tail) = rest.split_at(end - start + 1);
+
+        let loop_samples = loop_frames.concat();
+
+        let mut samples = intro.concat();
+        for _ in 0..repeats {
+            samples.extend_from_slice(&loop_samples);
+        }
+        samples.extend(tail.concat());
+
+        Ok(samples.into_iter())
     }
 
-    fn read_is_fourcc(&mut self) -> Result<bool, Error> {
-        let fourcc = self.read_fourcc()?;
-        self.seek(SeekFrom::Current(-4))?;
+    /// Reads the remaining 16-bit samples into one vector per channel.
+    pub fn read_all_channels_i16(&mut self) -> Result<Vec<Vec<i16>>, Error> {
+        let mut channels = vec![vec![]; self.fmt_chunk.num_channels as usize];
 
-        Ok(if let FourCC::Other(_) = fourcc {
-            false
-        } else {
-            true
-        })
+        for frame in self.frames_i16()? {
+            for (channel, sample) in channels.iter_mut().zip(frame) {
+                channel.push(sample);
+            }
+        }
+
+        Ok(channels)
     }
-}
 
-#[derive(Debug)]
-pub struct RiffChunk {
-    pub id: FourCC,
-    pub file_size: u32,
-    pub file_type: FourCC,
-}
+    /// Averages the channels of each remaining frame into a mono track,
+    /// rounding to the nearest sample.
+    pub fn downmix_mono_i16(&mut self) -> Result<Vec<i16>, Error> {
+        Ok(self
+            .frames_i16()?
+            .map(|frame| {
+                let sum = frame.iter().map(|sample| *sample as i32).sum::<i32>();
 
-#[derive(Debug)]
-pub struct FmtChunk {
-    pub id: FourCC,
-    pub data_size: u32,
-    pub format: Format,
-    pub num_channels: u16,
-    pub sample_rate: u32,
-    pub byte_rate: u32,
-    pub block_align: u16,
-    pub bits_per_raw_sample: u16,
-    pub extra_info_size: u16,
-    pub extended_info: Option<ExtendedInfo>,
-}
+                (sum as f64 / frame.len() as f64).round() as i16
+            })
+            .collect())
+    }
 
-#[derive(Debug)]
-pub struct ExtendedInfo {
-    pub bits_per_coded_sample: u16,
-    pub channel_mask: u32,
-    pub sub_format: u128,
-    pub remaining_data: Vec<u8>,
-}
+    /// Finds the ranges of remaining 16-bit frames where every channel stays
+    /// below `threshold` in magnitude for at least `min_frames`. Each range is
+    /// a start and exclusive end frame, counted from the start of the data so
+    /// they can be passed to [`seek_to_frame`](Self::seek_to_frame).
+    pub fn find_silence(
+        &mut self,
+        threshold: i16,
+        min_frames: u64,
+    ) -> Result<Vec<(u64, u64)>, Error> {
+        let first_frame = self
+            .position()?
+            .saturating_sub(self.data_offset)
+            .checked_div(self.fmt_chunk.block_align as u64)
+            .unwrap_or(0);
+        let threshold = (threshold as i32).abs();
 
-#[derive(Debug)]
-pub struct FactChunk {
-    pub id: FourCC,
-    pub data_size: u32,
-    pub sample_length: u32,
-    pub remaining_data: Vec<u8>,
-}
+        let mut ranges = vec![];
+        let mut silence_start = None;
+        let mut frame = first_frame;
 
-#[derive(Debug)]
-pub struct OtherChunk {
-    pub id: FourCC,
-    pub data_size: u32,
-    pub data: Vec<u8>,
-}
+        for samples in self.frames_i16()? {
+            let is_silent = samples
+                .iter()
+                .all(|sample| (*sample as i32).abs() < threshold);
 
-#[derive(Debug)]
-pub struct DataChunk {
-    pub id: FourCC,
-    pub data_size: u32,
-    pub pad_byte: u8,
-}
+            match (is_silent, silence_start) {
+                (true, None) => silence_start = Some(frame),
+                (false, Some(start)) => {
+                    if frame - start >= min_frames {
+                        ranges.push((start, frame));
+                    }
 
-#[derive(Debug, PartialEq, Clone)]
-pub enum FourCC {
-    Riff,
-    Fmt,
-    Data,
-    Wave,
-    Fact,
-    Other(String),
-}
+                    silence_start = None;
+                }
+                _ => {}
+            }
 
-impl From<&[u8]> for FourCC {
-    #[allow(clippy::unreadable_literal)]
-    fn from(data: &[u8]) -> Self {
-        match data {
-            b"RIFF" => FourCC::Riff,
-            b"WAVE" => FourCC::Wave,
-            b"fmt " => FourCC::Fmt,
-            b"data" => FourCC::Data,
-            b"Data" => FourCC::Data,
-            b"fact" => FourCC::Fact,
-            _ => {
-                let fourcc = unsafe { std::str::from_utf8_unchecked(&data) };
-                FourCC::Other(fourcc.to_owned())
+            frame += 1;
+        }
+
+        if let Some(start) = silence_start {
+            if frame - start >= min_frames {
+                ranges.push((start, frame));
             }
         }
+
+        Ok(ranges)
     }
-}
 
-#[derive(Debug, Copy, Clone)]
-pub enum Format {
-    UncompressedPCM,
-    IeeeFloatingPoint,
-    G711ALaw,
-    G711ULaw,
-    ExtendedWave,
-    Other(u16),
-}
+    pub fn samples_f32(&mut self) -> Result<impl Iterator<Item = f32>, Error> {
+        let data = self.read_samples(Format::IeeeFloatingPoint, 32)?;
+        let endianness = self.endianness;
 
-impl From<u16> for Format {
-    fn from(format: u16) -> Self {
-        match format {
-            1 => Format::UncompressedPCM,
-            3 => Format::IeeeFloatingPoint,
-            6 => Format::G711ALaw,
-            7 => Format::G711ULaw,
-            65534 => Format::ExtendedWave,
-            _ => Format::Other(format),
-        }
+        Ok(data
+            .chunks_exact(4)
+            .map(|sample| endianness.read_f32(sample))
+            .collect::<Vec<_>>()
+            .into_iter())
     }
-}
 
-impl<T: Read + Seek> std::fmt::Display for RiffWaveReader<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let size = self.riff_chunk.file_size;
-        let format = self.fmt_chunk.format;
-        let num_channels = self.fmt_chunk.num_channels;
-        let sample_rate = self.fmt_chunk.sample_rate;
-        let byte_rate = self.fmt_chunk.byte_rate;
-        let block_align = self.fmt_chunk.block_align;
+    pub fn samples_f64(&mut self) -> Result<impl Iterator<Item = f64>, Error> {
+        let data = self.read_samples(Format::IeeeFloatingPoint, 64)?;
+        let endianness = self.endianness;
+
+        Ok(data
+            .chunks_exact(8)
+            .map(|sample| endianness.read_f64(sample))
+            .collect::<Vec<_>>()
+            .into_iter())
+    }
+
+    /// Reads the remaining samples into a buffer of the type matching the
+    /// format and bit depth of the fmt chunk.
+    pub fn read_all(&mut self) -> Result<Samples, Error> {
+        let format = self.fmt_chunk.effective_format();
         let bits_per_sample = self.fmt_chunk.bits_per_raw_sample;
-        let extra_info_size = self.fmt_chunk.extra_info_size;
 
-        let extended = if let Some(extended) = &self.fmt_chunk.extended_info {
-            format!(
-                "\n----- Extended -----
-Bits per Coded:  {}
-Channel Mask:    {:#018b}
-Sub Format:      {:x}
-Remaining Data:  {:x?}",
-                extended.bits_per_coded_sample,
-                extended.channel_mask,
-                extended.sub_format,
-                extended.remaining_data,
-            )
-        } else {
-            String::from("")
-        };
+        Ok(match (format, bits_per_sample) {
+            (Format::UncompressedPCM, 8) => Samples::U8(self.read_samples(format, 8)?),
+            (Format::UncompressedPCM, 16) => Samples::I16(self.samples_i16()?.collect()),
+            (Format::UncompressedPCM, 24) => Samples::I24(self.samples_i24()?.collect()),
+            (Format::UncompressedPCM, 32) => {
+                let data = self.read_samples(format, 32)?;
+                let endianness = self.endianness;
 
-        let fact = if let Some(fact) = &self.fact_chunk {
-            format!(
-                "\n------- Fact -------
-Fact Length:     {}
-Sample Length:   {}
+                Samples::I32(
+                    data.chunks_exact(4)
+                        .map(|sample| endianness.read_i32(sample))
+                        .collect(),
+                )
+            }
+            (Format::IeeeFloatingPoint, 32) => Samples::F32(self.samples_f32()?.collect()),
+            (Format::IeeeFloatingPoint, 64) => Samples::F64(self.samples_f64()?.collect()),
+            (Format::UncompressedPCM, _) | (Format::IeeeFloatingPoint, _) => {
+                return Err(Error::UnsupportedBitDepth(bits_per_sample))
+            }
+            _ => return Err(Error::UnsupportedFormat(format)),
+        })
+    }
+
+    /// Returns the samples scaled to the range `[-1.0, 1.0]`, reading 8, 16, 24
+    /// or 32-bit PCM and 32 or 64-bit float data.
+    pub fn samples_normalized(&mut self) -> Result<impl Iterator<Item = f32>, Error> {
+        let format = self.fmt_chunk.effective_format();
+        let bits_per_sample = self.fmt_chunk.bits_per_raw_sample;
+
+        let normalize: fn(Endianness, &[u8]) -> f32 = match (format, bits_per_sample) {
+            (Format::UncompressedPCM, 8) => |_, sample| (sample[0] as f32 - 128.0) / 128.0,
+            (Format::UncompressedPCM, 16) => {
+                |endianness, sample| endianness.read_i16(sample) as f32 / 32_768.0
+            }
+            (Format::UncompressedPCM, 24) => {
+                |endianness, sample| endianness.read_i24(sample) as f32 / 8_388_608.0
+            }
+            (Format::UncompressedPCM, 32) => {
+                |endianness, sample| (endianness.read_i32(sample) as f64 / 2_147_483_648.0) as f32
+            }
+            (Format::IeeeFloatingPoint, 32) => Endianness::read_f32,
+            (Format::IeeeFloatingPoint, 64) => {
+                |endianness, sample| endianness.read_f64(sample) as f32
+            }
+            (Format::UncompressedPCM, _) | (Format::IeeeFloatingPoint, _) => {
+                return Err(Error::UnsupportedBitDepth(bits_per_sample))
+            }
+            _ => return Err(Error::UnsupportedFormat(format)),
+        };
+
+        let bytes_per_sample = self.fmt_chunk.bytes_per_sample()?;
+        let data = self.read_samples(format, bits_per_sample)?;
+        let endianness = self.endianness;
+
+        Ok(data
+            .chunks_exact(bytes_per_sample as usize)
+            .map(|sample| normalize(endianness, sample))
+            .collect::<Vec<_>>()
+            .into_iter())
+    }
+
+    /// Counts the remaining PCM samples sitting at the positive or negative
+    /// full scale value for the bit depth, e.g. 32767 or -32768 for 16-bit.
+    pub fn count_clipped_samples(&mut self) -> Result<u64, Error> {
+        let bits_per_sample = self.fmt_chunk.bits_per_raw_sample;
+
+        let is_clipped: fn(Endianness, &[u8]) -> bool = match bits_per_sample {
+            8 => |_, sample| sample[0] == u8::MIN || sample[0] == u8::MAX,
+            16 => |endianness, sample| matches!(endianness.read_i16(sample), i16::MIN | i16::MAX),
+            24 => {
+                |endianness, sample| matches!(endianness.read_i24(sample), -8_388_608 | 8_388_607)
+            }
+            32 => |endianness, sample| matches!(endianness.read_i32(sample), i32::MIN | i32::MAX),
+            _ => return Err(Error::UnsupportedBitDepth(bits_per_sample)),
+        };
+
+        let data = self.read_samples(Format::UncompressedPCM, bits_per_sample)?;
+        let endianness = self.endianness;
+
+        Ok(data
+            .chunks_exact(bits_per_sample as usize / 8)
+            .filter(|sample| is_clipped(endianness, sample))
+            .count() as u64)
+    }
+
+    pub fn num_frames(&self) -> u64 {
+        self.data_chunk.num_frames(self.fmt_chunk.block_align)
+    }
+
+    pub fn audio_spec(&self) -> AudioSpec {
+        self.fmt_chunk.audio_spec()
+    }
+
+    /// Returns the total frames of the data and silence segments of a `wavl`
+    /// list, or `None` if the file has none.
+    pub fn wave_segment_frames(&self) -> Option<u64> {
+        let block_align = self.fmt_chunk.block_align;
+
+        self.wave_segments.as_ref().map(|segments| {
+            segments
+                .iter()
+                .map(|segment| segment.num_frames(block_align))
+                .sum()
+        })
+    }
+
+    /// Checks that the size declared in the riff chunk matches the length of
+    /// the file, measured from its riff header to the end of the stream. A
+    /// file followed by another riff file, as in a concatenated stream, only
+    /// has to end where the next one starts. The reader position is restored
+    /// afterwards.
+    pub fn verify_file_size(&mut self) -> Result<(), Error> {
+        let declared = self.riff_size() + 8;
+
+        let position = self.reader.stream_position()?;
+        let actual = self.reader.seek(SeekFrom::End(0))? - self.riff_offset;
+        let next_offset = self.next_file_offset();
+        let is_followed_by_file = next_offset + 12 <= self.riff_offset + actual && {
+            let mut id = [0; 4];
+            self.reader.seek(SeekFrom::Start(next_offset))?;
+            self.reader.read_exact(&mut id)?;
+
+            matches!(
+                FourCC::from(id),
+                FourCC::Riff | FourCC::Rifx | FourCC::Rf64 | FourCC::Bw64
+            )
+        };
+        self.reader.seek(SeekFrom::Start(position))?;
+
+        if declared != actual && !is_followed_by_file {
+            return Err(Error::FileSizeMismatch { declared, actual });
+        }
+
+        Ok(())
+    }
+
+    pub fn duration(&self) -> Duration {
+        let num_frames = self.num_frames();
+        let sample_rate = self.fmt_chunk.sample_rate as u64;
+
+        let secs = match num_frames.checked_div(sample_rate) {
+            Some(secs) => secs,
+            None => return Duration::ZERO,
+        };
+
+        // The remainder is below the u32 sample rate, so this can't overflow
+        let nanos = (num_frames % sample_rate) * 1_000_000_000 / sample_rate;
+
+        Duration::new(secs, nanos as u32)
+    }
+
+    pub fn get_chunk(&self, id: &str) -> Option<&OtherChunk> {
+        self.other_chunks
+            .iter()
+            .find(|chunk| matches!(&chunk.id, FourCC::Other(other) if other == id))
+    }
+
+    pub fn get_chunks<'a>(&'a self, id: &'a str) -> impl Iterator<Item = &'a OtherChunk> + 'a {
+        self.other_chunks
+            .iter()
+            .filter(move |chunk| matches!(&chunk.id, FourCC::Other(other) if other == id))
+    }
+
+    pub fn info_tags(&self) -> Result<Option<Vec<InfoTag>>, Error> {
+        let mut info_tags: Option<Vec<InfoTag>> = None;
+
+        for chunk in self.get_chunks("LIST") {
+            if let Some(tags) = info::read_info_tags(&chunk.data, self.endianness)? {
+                info_tags.get_or_insert_with(Vec::new).extend(tags);
+            }
+        }
+
+        Ok(info_tags)
+    }
+
+    /// Returns the `labl` text of the given cue point, if the `adtl` list has
+    /// one.
+    pub fn cue_label(&self, cue_point: &CuePoint) -> Option<&str> {
+        self.adtl
+            .iter()
+            .flatten()
+            .find(|entry| {
+                entry.cue_point_id == cue_point.id && matches!(entry.kind, AdtlKind::Label(_))
+            })
+            .map(AdtlEntry::text)
+    }
+
+    pub fn print_info(&self) {
+        println!("{}", self);
+    }
+
+    pub fn into_reader(self) -> T {
+        self.reader
+    }
+
+    /// Returns the underlying reader along with the parsed chunks.
+    pub fn into_parts(
+        self,
+    ) -> (
+        T,
+        RiffChunk,
+        FmtChunk,
+        Option<FactChunk>,
+        DataChunk,
+        Vec<OtherChunk>,
+    ) {
+        (
+            self.reader,
+            self.riff_chunk,
+            self.fmt_chunk,
+            self.fact_chunk,
+            self.data_chunk,
+            self.other_chunks,
+        )
+    }
+
+    /// Parses the next riff file in a stream of concatenated files, starting
+    /// after the end of this one. Returns `None` if this file ends the
+    /// stream.
+    pub fn next_file(mut self) -> Result<Option<RiffWaveReader<T>>, Error> {
+        let next_offset = self.next_file_offset();
+
+        if next_offset >= self.reader.seek(SeekFrom::End(0))? {
+            return Ok(None);
+        }
+
+        self.reader.seek(SeekFrom::Start(next_offset))?;
+
+        RiffWaveReader::with_options(self.reader, self.options).map(Some)
+    }
+
+    /// Returns the offset following the riff chunk and its pad byte.
+    fn next_file_offset(&self) -> u64 {
+        let riff_size = self.riff_size();
+
+        self.riff_offset
+            .saturating_add(8)
+            .saturating_add(riff_size)
+            .saturating_add(riff_size % 2)
+    }
+
+    fn riff_size(&self) -> u64 {
+        riff_size(&self.riff_chunk, self.ds64_chunk.as_ref())
+    }
+
+    fn data_end(&self) -> u64 {
+        data_end(self.data_offset, &self.data_chunk)
+    }
+
+    fn validate_strict(&mut self) -> Result<(), Error> {
+        match self.spec_violations()?.into_iter().next() {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns every spec violation that [`ParseOptions::strict`] rejects,
+    /// along with any chunks skipped for their size or whose metadata failed
+    /// to parse. Only errors from the underlying reader are returned as `Err`.
+    fn spec_violations(&mut self) -> Result<Vec<Error>, Error> {
+        let mut violations = vec![];
+
+        if !self.has_fmt_chunk {
+            violations.push(Error::parse(
+                self.data_offset - 8,
+                ParseKind::MissingFmtChunk,
+            ));
+        } else if let Err(e) = self.fmt_chunk.validate() {
+            violations.push(e);
+        }
+
+        if self.is_truncated() {
+            violations.push(Error::TruncatedData {
+                declared: self.data_chunk.data_size,
+                available: self.available_data,
+            });
+        }
+
+        match self.verify_file_size() {
+            Ok(()) => {}
+            Err(e @ Error::FileSizeMismatch { .. }) => violations.push(e),
+            Err(e) => return Err(e),
+        }
+
+        violations.extend(
+            self.chunk_layout
+                .iter()
+                .filter(|chunk| chunk.offset % 2 == 1)
+                .map(|chunk| Error::parse(chunk.offset, ParseKind::MisalignedChunk)),
+        );
+
+        violations.extend(
+            self.metadata_errors
+                .iter()
+                .map(|(offset, kind)| Error::parse(*offset, kind.clone())),
+        );
+
+        violations.extend(
+            self.skipped_chunks.iter().map(|chunk| {
+                Error::parse(chunk.offset, ParseKind::SkippedChunk { size: chunk.size })
+            }),
+        );
+
+        let format = self.fmt_chunk.effective_format();
+        if self.has_fmt_chunk && format != Format::UncompressedPCM && self.fact_chunk.is_none() {
+            violations.push(Error::parse(
+                self.data_offset - 8,
+                ParseKind::MissingFactChunk,
+            ));
+        }
+
+        Ok(violations)
+    }
+
+    fn read_samples(&mut self, format: Format, bits_per_sample: u16) -> Result<Vec<u8>, Error> {
+        self.check_format(format, bits_per_sample)?;
+
+        let data = self.read_data()?;
+
+        if data.len() % (bits_per_sample / 8) as usize != 0 {
+            return Err(Error::IncompleteSample);
+        }
+
+        Ok(data)
+    }
+
+    fn check_format(&self, format: Format, bits_per_sample: u16) -> Result<(), Error> {
+        let fmt_chunk = &self.fmt_chunk;
+
+        if fmt_chunk.effective_format() != format {
+            return Err(Error::UnsupportedFormat(fmt_chunk.effective_format()));
+        }
+
+        let bytes_per_sample = bits_per_sample / 8;
+
+        if fmt_chunk.bits_per_raw_sample != bits_per_sample
+            || fmt_chunk.block_align as u32
+                != fmt_chunk.num_channels as u32 * bytes_per_sample as u32
+        {
+            return Err(Error::UnsupportedBitDepth(fmt_chunk.bits_per_raw_sample));
+        }
+
+        Ok(())
+    }
+
+    fn read_data(&mut self) -> Result<Vec<u8>, Error> {
+        let remaining = self.remaining_data()?;
+
+        let mut data = vec![];
+        (&mut self.reader).take(remaining).read_to_end(&mut data)?;
+
+        if remaining > 0 {
+            self.reader
+                .seek(SeekFrom::Current(self.data_chunk.pad_byte as i64))?;
+        }
+
+        Ok(data)
+    }
+
+    /// Returns the number of data bytes after the current position, so reads
+    /// stop at the end of the data chunk instead of running into the chunks
+    /// following it.
+    fn remaining_data(&mut self) -> Result<u64, Error> {
+        let data_end = self.data_offset.saturating_add(self.available_data);
+
+        Ok(data_end.saturating_sub(self.reader.stream_position()?))
+    }
+}
+
+#[cfg(feature = "std")]
+impl RiffWaveReader<BufReader<File>> {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<RiffWaveReader<BufReader<File>>, Error> {
+        let file = File::open(path)?;
+
+        RiffWaveReader::new(BufReader::new(file))
+    }
+}
+
+#[cfg(feature = "mmap")]
+impl RiffWaveReader<Cursor<memmap2::Mmap>> {
+    /// Memory maps the file instead of reading it through a buffer, which
+    /// avoids repeated seeks on large files. The file must not be modified
+    /// while the reader is alive.
+    pub fn open_mmap<P: AsRef<Path>>(
+        path: P,
+    ) -> Result<RiffWaveReader<Cursor<memmap2::Mmap>>, Error> {
+        let file = File::open(path)?;
+
+        // Safety: the map is read-only and callers must not modify the file
+        // while it is mapped
+        let mmap = unsafe { memmap2::Mmap::map(&file)? };
+
+        RiffWaveReader::new(Cursor::new(mmap))
+    }
+}
+
+#[cfg(feature = "hound-interop")]
+impl<T: Read + Seek> RiffWaveReader<T> {
+    /// Maps the resolved [`audio_spec`](Self::audio_spec) to the spec used by
+    /// the `hound` crate. Floating point data maps to
+    /// [`hound::SampleFormat::Float`], every other format to
+    /// [`hound::SampleFormat::Int`].
+    pub fn to_hound_spec(&self) -> hound::WavSpec {
+        let spec = self.audio_spec();
+
+        let sample_format = match spec.format {
+            Format::IeeeFloatingPoint => hound::SampleFormat::Float,
+            _ => hound::SampleFormat::Int,
+        };
+
+        hound::WavSpec {
+            channels: spec.channels,
+            sample_rate: spec.sample_rate,
+            bits_per_sample: spec.bits_per_sample,
+            sample_format,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: AsRef<[u8]>> RiffWaveReader<Cursor<T>> {
+    /// Returns the data block without copying it out of the underlying
+    /// buffer.
+    pub fn data_slice(&self) -> &[u8] {
+        let bytes = self.reader.get_ref().as_ref();
+
+        let start = (self.data_offset as usize).min(bytes.len());
+        let end = self
+            .data_offset
+            .saturating_add(self.data_chunk.data_size)
+            .min(bytes.len() as u64) as usize;
+
+        &bytes[start..end]
+    }
+}
+
+#[cfg(feature = "std")]
+impl RiffWaveReader<Cursor<Vec<u8>>> {
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<RiffWaveReader<Cursor<Vec<u8>>>, Error> {
+        RiffWaveReader::new(Cursor::new(bytes))
+    }
+}
+
+#[cfg(feature = "std")]
+trait ReadExt: Read + Seek {
+    fn read_other_chunk(&mut self, header: ChunkHeader) -> Result<OtherChunk, Error>;
+
+    fn read_fourcc(&mut self) -> Result<FourCC, Error>;
+
+    fn read_fourcc_or_eof(&mut self) -> Result<Option<FourCC>, Error>;
+
+    fn read_u32(&mut self, endianness: Endianness) -> Result<u32, Error>;
+
+    fn read_u16(&mut self, endianness: Endianness) -> Result<u16, Error>;
+
+    fn read_u24(&mut self, endianness: Endianness) -> Result<u32, Error>;
+
+    fn read_bytes(&mut self, len: usize) -> Result<Vec<u8>, Error>;
+
+    fn read_string(&mut self, len: usize) -> Result<String, Error>;
+}
+
+#[cfg(feature = "std")]
+impl<T: Read + Seek> ReadExt for T {
+    fn read_other_chunk(&mut self, header: ChunkHeader) -> Result<OtherChunk, Error> {
+        let ChunkHeader { id, data_size, .. } = header;
+
+        let mut data = vec![0; data_size as usize];
+        self.read_exact(&mut data)?;
+
+        if data_size % 2 == 1 {
+            self.seek(SeekFrom::Current(1))?;
+        }
+
+        Ok(OtherChunk {
+            id,
+            data_size,
+            data,
+        })
+    }
+
+    fn read_fourcc(&mut self) -> Result<FourCC, Error> {
+        let mut buf = [0; 4];
+
+        self.read_exact(&mut buf)?;
+
+        Ok(FourCC::from(buf))
+    }
+
+    fn read_fourcc_or_eof(&mut self) -> Result<Option<FourCC>, Error> {
+        match self.read_fourcc() {
+            Ok(fourcc) => Ok(Some(fourcc)),
+            Err(Error::IOError(e)) if e.kind() == io::ErrorKind::UnexpectedEof => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn read_u32(&mut self, endianness: Endianness) -> Result<u32, Error> {
+        let mut buf = [0; 4];
+
+        self.read_exact(&mut buf)?;
+
+        Ok(match endianness {
+            Endianness::Little => LittleEndian::read_u32(&buf),
+            Endianness::Big => BigEndian::read_u32(&buf),
+        })
+    }
+
+    fn read_u16(&mut self, endianness: Endianness) -> Result<u16, Error> {
+        let mut buf = [0; 2];
+
+        self.read_exact(&mut buf)?;
+
+        Ok(match endianness {
+            Endianness::Little => LittleEndian::read_u16(&buf),
+            Endianness::Big => BigEndian::read_u16(&buf),
+        })
+    }
+
+    fn read_u24(&mut self, endianness: Endianness) -> Result<u32, Error> {
+        let mut buf = [0; 3];
+
+        self.read_exact(&mut buf)?;
+
+        Ok(match endianness {
+            Endianness::Little => LittleEndian::read_u24(&buf),
+            Endianness::Big => BigEndian::read_u24(&buf),
+        })
+    }
+
+    fn read_bytes(&mut self, len: usize) -> Result<Vec<u8>, Error> {
+        let mut buf = vec![0; len];
+
+        self.read_exact(&mut buf)?;
+
+        Ok(buf)
+    }
+
+    fn read_string(&mut self, len: usize) -> Result<String, Error> {
+        let buf = self.read_bytes(len)?;
+
+        Ok(String::from_utf8_lossy(&buf)
+            .trim_end_matches('\0')
+            .to_owned())
+    }
+}
+
+/// Adapts a `Read + Seek` stream to a [`Source`], so the chunks of a file
+/// are parsed by the same [`SourceExt`] parsers with or without `std`.
+#[cfg(feature = "std")]
+struct SeekSource<'a, T: Read + Seek> {
+    reader: &'a mut T,
+    position: u64,
+    /// The IO error behind the last failed read, which [`HeaderError`] only
+    /// keeps the kind of.
+    error: Option<io::Error>,
+}
+
+#[cfg(feature = "std")]
+impl<T: Read + Seek> SeekSource<'_, T> {
+    fn fail(&mut self, e: io::Error) -> HeaderError {
+        let error = HeaderError::from(io::Error::from(e.kind()));
+        self.error = Some(e);
+
+        error
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Read + Seek> Source for SeekSource<'_, T> {
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), HeaderError> {
+        self.reader.read_exact(buf).map_err(|e| self.fail(e))?;
+        self.position += buf.len() as u64;
+
+        Ok(())
+    }
+
+    fn skip(&mut self, len: u64) -> Result<(), HeaderError> {
+        self.position = self
+            .reader
+            .seek(SeekFrom::Current(len as i64))
+            .map_err(|e| self.fail(e))?;
+
+        Ok(())
+    }
+
+    fn position(&self) -> u64 {
+        self.position
+    }
+}
+
+/// Runs a [`SourceExt`] parser on `reader`, reporting its errors at `offset`
+/// and the stream ending early as the end of `context`.
+#[cfg(feature = "std")]
+fn parse_source<T: Read + Seek, V>(
+    reader: &mut T,
+    offset: u64,
+    context: &'static str,
+    parse: impl FnOnce(&mut SeekSource<'_, T>) -> Result<V, HeaderError>,
+) -> Result<V, Error> {
+    let position = reader.stream_position()?;
+    let mut source = SeekSource {
+        reader,
+        position,
+        error: None,
+    };
+
+    parse(&mut source).map_err(|e| match source.error.take() {
+        Some(io_error) => Error::from(io_error).eof_context(offset, context),
+        None => Error::from_header(e, offset, context),
+    })
+}
+
+/// Sign extends a 24-bit sample held in the low bytes of a `u32`.
+#[cfg(feature = "std")]
+fn sign_extend_i24(value: u32) -> i32 {
+    ((value << 8) as i32) >> 8
+}
+
+#[cfg(feature = "std")]
+fn hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(feature = "std")]
+fn data_end(data_offset: u64, data_chunk: &DataChunk) -> u64 {
+    data_offset
+        .saturating_add(data_chunk.data_size)
+        .saturating_add(data_chunk.pad_byte as u64)
+}
+
+/// The chunks preceding the data, as returned by
+/// [`RiffWaveReader::new_headers_only`].
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+pub struct RiffWaveHeaders {
+    pub riff_chunk: RiffChunk,
+    pub fmt_chunk: FmtChunk,
+    pub fact_chunk: Option<FactChunk>,
+    pub ds64_chunk: Option<Ds64Chunk>,
+    pub data_chunk: Option<DataChunk>,
+    pub other_chunks: Vec<OtherChunk>,
+    pub bext: Option<BextChunk>,
+    pub cart: Option<CartChunk>,
+    pub cue_points: Option<Vec<CuePoint>>,
+    pub adtl: Option<Vec<AdtlEntry>>,
+    pub smpl: Option<SmplChunk>,
+    pub peak: Option<PeakChunk>,
+    pub ixml: Option<String>,
+    pub acid: Option<AcidChunk>,
+    /// The raw ID3v2 tag of an `id3 ` or `ID3 ` chunk.
+    pub id3: Option<Vec<u8>>,
+    /// The segments of a `wavl` list. Files storing their waveform this way
+    /// have no data chunk, so `data_chunk` is empty.
+    pub wave_segments: Option<Vec<WaveSegment>>,
+    pub endianness: Endianness,
+    riff_offset: u64,
+    chunk_layout: Vec<ChunkInfo>,
+    skipped_chunks: Vec<ChunkInfo>,
+    metadata_errors: Vec<(u64, ParseKind)>,
+    has_fmt_chunk: bool,
+}
+
+/// Reads the chunks up to and including the header of the data chunk,
+/// leaving the reader on the first data byte. Reaching the end of the stream
+/// first is an error only if `require_data` is set.
+#[cfg(feature = "std")]
+fn read_headers<T: Read + Seek>(
+    reader: &mut T,
+    options: &ParseOptions,
+    require_data: bool,
+) -> Result<RiffWaveHeaders, Error> {
+    let riff_offset = reader.stream_position()?;
+    let riff_chunk = parse_source(reader, riff_offset, "riff header", |source| {
+        source.read_riff_chunk()
+    })?;
+
+    let is_64 = match riff_chunk.id {
+        FourCC::Riff | FourCC::Rifx => false,
+        FourCC::Rf64 | FourCC::Bw64 => true,
+        _ => return Err(Error::parse(riff_offset, ParseKind::NotRiff)),
+    };
+
+    if riff_chunk.file_type != FourCC::Wave {
+        return Err(Error::parse(riff_offset + 8, ParseKind::NotWave));
+    }
+
+    let endianness = riff_chunk.endianness();
+
+    let mut chunk_layout = vec![];
+    let mut skipped_chunks = vec![];
+
+    let ds64_chunk = if is_64 {
+        let offset = riff_offset + 12;
+        let header = parse_source(reader, offset, "ds64 chunk", |source| {
+            source.read_chunk_header(endianness)
+        })?;
+
+        match header {
+            Some(header) if header.id == FourCC::Ds64 => {
+                check_chunk_size(header.data_size, options.max_chunk_size)?;
+                chunk_layout.push(ChunkInfo::from(&header));
+                Some(parse_source(reader, offset, "ds64 chunk", |source| {
+                    source.read_ds64_chunk(header, endianness)
+                })?)
+            }
+            _ => return Err(Error::parse(riff_offset + 12, ParseKind::InvalidDs64Chunk)),
+        }
+    } else {
+        None
+    };
+
+    let mut fmt_chunk = None;
+    let mut fact_chunk = None;
+    let mut other_chunks = vec![];
+    let mut metadata = Metadata::default();
+
+    let mut data_chunk = loop {
+        let mut offset = reader.stream_position()?;
+
+        let after_odd_chunk = chunk_layout.last().is_some_and(|chunk| chunk.size % 2 == 1);
+        if options.recover_missing_padding && after_odd_chunk {
+            offset = recover_missing_pad(reader, offset)?;
+        }
+
+        let header = parse_source(reader, offset, "chunk header", |source| {
+            source.read_chunk_header(endianness)
+        })?;
+
+        let header = match header {
+            Some(header) => header,
+            None if metadata.wave_segments.is_some() => break Some(DataChunk::empty()),
+            None if require_data => {
+                return Err(Error::parse(offset, ParseKind::MissingDataChunk));
+            }
+            None => break None,
+        };
+
+        chunk_layout.push(ChunkInfo::from(&header));
+
+        if options.skip_padding_chunks && matches!(&header.raw_id, b"JUNK" | b"junk" | b"PAD ") {
+            let size = header.data_size as i64 + (header.data_size % 2) as i64;
+            reader.seek(SeekFrom::Current(size))?;
+
+            continue;
+        }
+
+        if header.id != FourCC::Data {
+            if options.skip_oversized_chunks && header.data_size > options.max_chunk_size {
+                let size = header.data_size as i64 + (header.data_size % 2) as i64;
+                reader.seek(SeekFrom::Current(size))?;
+                skipped_chunks.push(ChunkInfo::from(&header));
+
+                continue;
+            }
+
+            check_chunk_size(header.data_size, options.max_chunk_size)?;
+        }
+
+        match header.id {
+            FourCC::Fmt => {
+                let chunk = parse_source(reader, offset, "fmt chunk", |source| {
+                    source.read_fmt_chunk(header, endianness)
+                })?;
+
+                fmt_chunk = Some(chunk);
+            }
+            FourCC::Fact => {
+                let chunk = parse_source(reader, offset, "fact chunk", |source| {
+                    source.read_fact_chunk(header, endianness)
+                })?;
+
+                fact_chunk = Some(chunk);
+            }
+            FourCC::Data => {
+                if fmt_chunk.is_none() && !options.recover_missing_fmt {
+                    return Err(Error::parse(offset, ParseKind::MissingFmtChunk));
+                }
+
+                break Some(DataChunk::from_header(&header));
+            }
+            _ => {
+                let chunk = reader
+                    .read_other_chunk(header)
+                    .map_err(|e| e.eof_context(offset, "chunk body"))?;
+
+                metadata.read(&chunk, offset, endianness);
+                other_chunks.push(chunk);
+            }
+        }
+    };
+
+    metadata.finish(endianness, fmt_chunk.as_ref());
+
+    let offset = reader.stream_position()?;
+    let has_fmt_chunk = fmt_chunk.is_some();
+    let fmt_chunk = match fmt_chunk {
+        Some(fmt_chunk) => fmt_chunk,
+        None if options.recover_missing_fmt => FmtChunk::placeholder(),
+        None => return Err(Error::parse(offset, ParseKind::MissingFmtChunk)),
+    };
+
+    if let (Some(ds64_chunk), Some(data_chunk)) = (&ds64_chunk, &mut data_chunk) {
+        if data_chunk.data_size == 0xffff_ffff {
+            data_chunk.data_size = ds64_chunk.data_chunk_size;
+            data_chunk.pad_byte = (ds64_chunk.data_chunk_size % 2) as u8;
+            data_chunk.is_unsized = false;
+        }
+    }
+
+    Ok(RiffWaveHeaders {
+        riff_chunk,
+        fmt_chunk,
+        fact_chunk,
+        ds64_chunk,
+        data_chunk,
+        other_chunks,
+        bext: metadata.bext,
+        cart: metadata.cart,
+        cue_points: metadata.cue_points,
+        adtl: metadata.adtl,
+        smpl: metadata.smpl,
+        peak: metadata.peak,
+        ixml: metadata.ixml,
+        acid: metadata.acid,
+        id3: metadata.id3,
+        wave_segments: metadata.wave_segments,
+        endianness,
+        riff_offset,
+        chunk_layout,
+        skipped_chunks,
+        metadata_errors: metadata.errors,
+        has_fmt_chunk,
+    })
+}
+
+/// Reads the headers of the chunks between the data chunk and `riff_end`,
+/// skipping their bodies. The scan stops early at the end of the stream or
+/// the first malformed header, since trailing chunks are not needed to read
+/// the data.
+#[cfg(feature = "std")]
+fn read_trailing_chunks<T: Read + Seek>(
+    reader: &mut T,
+    data_end: u64,
+    riff_end: u64,
+    endianness: Endianness,
+) -> Vec<ChunkInfo> {
+    let mut chunks = vec![];
+    let mut offset = data_end;
+
+    while offset.saturating_add(8) <= riff_end {
+        if reader.seek(SeekFrom::Start(offset)).is_err() {
+            break;
+        }
+
+        let header = parse_source(reader, offset, "chunk header", |source| {
+            source.read_chunk_header(endianness)
+        });
+
+        let header = match header {
+            Ok(Some(header)) => header,
+            _ => break,
+        };
+
+        let size = header.data_size as u64 + (header.data_size % 2) as u64;
+        offset = offset.saturating_add(8 + size);
+        chunks.push(ChunkInfo::from(&header));
+    }
+
+    chunks
+}
+
+/// Returns the size declared by the riff chunk, taken from the ds64 chunk of
+/// RF64 and BW64 files.
+#[cfg(feature = "std")]
+fn riff_size(riff_chunk: &RiffChunk, ds64_chunk: Option<&Ds64Chunk>) -> u64 {
+    match ds64_chunk {
+        Some(ds64_chunk) if riff_chunk.file_size == 0xffff_ffff => ds64_chunk.riff_size,
+        _ => riff_chunk.file_size as u64,
+    }
+}
+
+/// Options controlling how [`RiffWaveReader`] handles files that violate the
+/// spec.
+#[cfg(feature = "std")]
+#[derive(Debug, Copy, Clone)]
+pub struct ParseOptions {
+    /// Rejects files with an inconsistent fmt chunk, a data chunk extending
+    /// past the end of the stream, a riff size that does not match the stream
+    /// length, chunks starting on an odd offset, a missing fact chunk for
+    /// non-PCM data, or metadata chunks that can't be parsed. When unset,
+    /// these are tolerated and malformed metadata chunks are only kept in
+    /// `other_chunks`.
+    pub strict: bool,
+    /// The largest chunk, other than the data chunk, that will be read into
+    /// memory. Larger chunks fail with [`Error::ChunkTooLarge`] instead of
+    /// allocating whatever size a malformed file declares.
+    pub max_chunk_size: u32,
+    /// The total length of the stream. An unsized data chunk is read up to
+    /// this length instead of the end reported by seeking the stream.
+    pub stream_length: Option<u64>,
+    /// Accepts a data chunk without a preceding fmt chunk, such as when the
+    /// fmt id is damaged. The reader then has a placeholder fmt chunk that no
+    /// samples can be read with until one is supplied with
+    /// [`RiffWaveReader::set_fmt_chunk`].
+    pub recover_missing_fmt: bool,
+    /// Seeks past the bodies of `JUNK` and `PAD ` chunks, which only align
+    /// the following chunks, instead of keeping them in `other_chunks`. They
+    /// are still listed in the chunk layout.
+    pub skip_padding_chunks: bool,
+    /// Reads a chunk from one byte earlier when the writer left out the pad
+    /// byte of the preceding odd sized chunk. Recovered chunks are listed at
+    /// their odd offset in the chunk layout.
+    pub recover_missing_padding: bool,
+    /// Seeks past chunks larger than `max_chunk_size` instead of failing with
+    /// [`Error::ChunkTooLarge`]. They are still listed in the chunk layout.
+    pub skip_oversized_chunks: bool,
+}
+
+#[cfg(feature = "std")]
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions {
+            strict: false,
+            max_chunk_size: DEFAULT_MAX_CHUNK_SIZE,
+            stream_length: None,
+            recover_missing_fmt: false,
+            skip_padding_chunks: false,
+            recover_missing_padding: false,
+            skip_oversized_chunks: false,
+        }
+    }
+}
+
+const DEFAULT_MAX_CHUNK_SIZE: u32 = 8 * 1024 * 1024;
+
+/// Returns the offset of the next chunk header, stepping back over a missing
+/// pad byte when the byte in its place, which should be zero, starts a chunk id.
+#[cfg(feature = "std")]
+fn recover_missing_pad<T: Read + Seek>(reader: &mut T, offset: u64) -> Result<u64, Error> {
+    let mut buf = [0; 5];
+    reader.seek(SeekFrom::Start(offset - 1))?;
+    let found = reader.read_exact(&mut buf).is_ok();
+
+    let offset = if found && is_chunk_id(&buf[..4]) {
+        offset - 1
+    } else {
+        offset
+    };
+
+    reader.seek(SeekFrom::Start(offset))?;
+
+    Ok(offset)
+}
+
+/// Returns true if a chunk header with a printable id and a body that fits in
+/// the stream starts at `offset`. The reader is left at an unspecified
+/// position.
+#[cfg(feature = "std")]
+fn is_chunk_header_at<T: Read + Seek>(
+    reader: &mut T,
+    offset: u64,
+    stream_end: u64,
+    endianness: Endianness,
+) -> Result<bool, Error> {
+    let mut buf = [0; 8];
+    reader.seek(SeekFrom::Start(offset))?;
+    if reader.read_exact(&mut buf).is_err() {
+        return Ok(false);
+    }
+
+    let size = match endianness {
+        Endianness::Little => LittleEndian::read_u32(&buf[4..]),
+        Endianness::Big => BigEndian::read_u32(&buf[4..]),
+    };
+
+    Ok(is_chunk_id(&buf[..4]) && offset + 8 + size as u64 <= stream_end)
+}
+
+#[cfg(feature = "std")]
+fn is_chunk_id(id: &[u8]) -> bool {
+    id.iter().all(|b| b.is_ascii_graphic() || *b == b' ')
+}
+
+#[cfg(feature = "std")]
+fn check_chunk_size(size: u32, limit: u32) -> Result<(), Error> {
+    if size > limit {
+        return Err(Error::ChunkTooLarge { size, limit });
+    }
+
+    Ok(())
+}
+
+/// Metadata parsed from chunks that are otherwise kept as [`OtherChunk`]s.
+#[cfg(feature = "std")]
+#[derive(Default)]
+struct Metadata {
+    bext: Option<BextChunk>,
+    cart: Option<CartChunk>,
+    cue_points: Option<Vec<CuePoint>>,
+    adtl: Option<Vec<AdtlEntry>>,
+    smpl: Option<SmplChunk>,
+    peak: Option<PeakChunk>,
+    ixml: Option<String>,
+    acid: Option<AcidChunk>,
+    id3: Option<Vec<u8>>,
+    wave_segments: Option<Vec<WaveSegment>>,
+    /// The offset and body of a PEAK chunk, which is parsed once the channel
+    /// count is known since it may precede the fmt chunk.
+    pending_peak: Option<(u64, Vec<u8>)>,
+    /// The offsets of the chunks that failed to parse and why.
+    errors: Vec<(u64, ParseKind)>,
+}
+
+#[cfg(feature = "std")]
+impl Metadata {
+    /// Parses the metadata of a chunk, if it is of a known kind. Chunks that
+    /// fail to parse leave their field unset and are recorded in `errors`.
+    fn read(&mut self, chunk: &OtherChunk, offset: u64, endianness: Endianness) {
+        let id = match &chunk.id {
+            FourCC::Other(id) => id.as_str(),
+            _ => return,
+        };
+
+        let data = &chunk.data;
+
+        match id {
+            "bext" => {
+                let bext = bext::read_bext_chunk(data, offset, endianness);
+                self.bext = self.check(bext, offset, "bext chunk");
+            }
+            "cart" => {
+                let cart = cart::read_cart_chunk(data, offset, endianness);
+                self.cart = self.check(cart, offset, "cart chunk");
+            }
+            "cue " => {
+                let cue_points = cue::read_cue_points(data, offset, endianness);
+                self.cue_points = self.check(cue_points, offset, "cue chunk");
+            }
+            "LIST" => {
+                let entries = adtl::read_adtl_entries(data, endianness);
+                if let Some(entries) = self.check(entries, offset, "adtl list").flatten() {
+                    self.adtl.get_or_insert_with(Vec::new).extend(entries);
+                }
+
+                let segments = wavl::read_wave_segments(data, offset, endianness);
+                if let Some(segments) = self.check(segments, offset, "wavl list").flatten() {
+                    self.wave_segments = Some(segments);
+                }
+            }
+            "smpl" => {
+                let smpl = smpl::read_smpl_chunk(data, offset, endianness);
+                self.smpl = self.check(smpl, offset, "smpl chunk");
+            }
+            "acid" => {
+                let acid = acid::read_acid_chunk(data, offset, endianness);
+                self.acid = self.check(acid, offset, "acid chunk");
+            }
+            "PEAK" => self.pending_peak = Some((offset, data.clone())),
+            "id3 " | "ID3 " => self.id3 = Some(data.clone()),
+            "iXML" => {
+                let ixml = String::from_utf8_lossy(data);
+
+                self.ixml = Some(
+                    ixml.trim_end_matches(|c: char| c == '\0' || c.is_whitespace())
+                        .to_owned(),
+                )
+            }
+            _ => {}
+        }
+    }
+
+    /// Parses the chunks that depend on the fmt chunk, once all the chunks
+    /// before the data have been read.
+    fn finish(&mut self, endianness: Endianness, fmt_chunk: Option<&FmtChunk>) {
+        if let Some((offset, data)) = self.pending_peak.take() {
+            let peak = match fmt_chunk {
+                Some(fmt_chunk) => {
+                    peak::read_peak_chunk(&data, offset, endianness, fmt_chunk.num_channels)
+                }
+                None => Err(Error::parse(offset, ParseKind::InvalidPeakChunk)),
+            };
+            self.peak = self.check(peak, offset, "PEAK chunk");
+        }
+    }
+
+    fn check<V>(
+        &mut self,
+        result: Result<V, Error>,
+        offset: u64,
+        context: &'static str,
+    ) -> Option<V> {
+        match result {
+            Ok(value) => Some(value),
+            Err(e) => {
+                self.errors.push(e.into_parse(offset, context));
+                None
+            }
+        }
+    }
+}
+
+struct ChunkHeader {
+    id: FourCC,
+    raw_id: [u8; 4],
+    data_size: u32,
+    offset: u64,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChunkInfo {
+    pub id: FourCC,
+    /// The id bytes as found in the file. These differ from `id` for ids that
+    /// are not valid UTF-8 and for a `Data` cased data chunk.
+    pub raw_id: [u8; 4],
+    pub offset: u64,
+    pub size: u32,
+}
+
+impl From<&ChunkHeader> for ChunkInfo {
+    fn from(header: &ChunkHeader) -> Self {
+        ChunkInfo {
+            id: header.id.clone(),
+            raw_id: header.raw_id,
+            offset: header.offset,
+            size: header.data_size,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RiffChunk {
+    pub id: FourCC,
+    pub file_size: u32,
+    pub file_type: FourCC,
+}
+
+impl RiffChunk {
+    pub fn endianness(&self) -> Endianness {
+        if self.id == FourCC::Rifx {
+            Endianness::Big
+        } else {
+            Endianness::Little
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Endianness {
+    Little,
+    Big,
+}
+
+/// Decoders for sample data, which follows the byte order of the file.
+#[cfg(feature = "std")]
+impl Endianness {
+    fn read_i16(self, buf: &[u8]) -> i16 {
+        match self {
+            Endianness::Little => LittleEndian::read_i16(buf),
+            Endianness::Big => BigEndian::read_i16(buf),
+        }
+    }
+
+    fn read_i16_into(self, buf: &[u8], dst: &mut [i16]) {
+        match self {
+            Endianness::Little => LittleEndian::read_i16_into(buf, dst),
+            Endianness::Big => BigEndian::read_i16_into(buf, dst),
+        }
+    }
+
+    fn read_i24(self, buf: &[u8]) -> i32 {
+        match self {
+            Endianness::Little => LittleEndian::read_i24(buf),
+            Endianness::Big => BigEndian::read_i24(buf),
+        }
+    }
+
+    fn read_i32(self, buf: &[u8]) -> i32 {
+        match self {
+            Endianness::Little => LittleEndian::read_i32(buf),
+            Endianness::Big => BigEndian::read_i32(buf),
+        }
+    }
+
+    fn read_f32(self, buf: &[u8]) -> f32 {
+        match self {
+            Endianness::Little => LittleEndian::read_f32(buf),
+            Endianness::Big => BigEndian::read_f32(buf),
+        }
+    }
+
+    fn read_f64(self, buf: &[u8]) -> f64 {
+        match self {
+            Endianness::Little => LittleEndian::read_f64(buf),
+            Endianness::Big => BigEndian::read_f64(buf),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FmtChunk {
+    pub id: FourCC,
+    pub data_size: u32,
+    pub format: Format,
+    pub num_channels: u16,
+    pub sample_rate: u32,
+    pub byte_rate: u32,
+    pub block_align: u16,
+    pub bits_per_raw_sample: u16,
+    pub extra_info_size: u16,
+    pub extended_info: Option<ExtendedInfo>,
+}
+
+impl FmtChunk {
+    /// Builds a plain 16 byte PCM fmt chunk, deriving `block_align` and
+    /// `byte_rate` from the channel count, sample rate and bit depth.
+    pub fn pcm(num_channels: u16, sample_rate: u32, bits_per_sample: u16) -> FmtChunk {
+        let block_align = num_channels.saturating_mul(bits_per_sample.div_ceil(8));
+
+        FmtChunk {
+            id: FourCC::Fmt,
+            data_size: 16,
+            format: Format::UncompressedPCM,
+            num_channels,
+            sample_rate,
+            byte_rate: sample_rate.saturating_mul(block_align as u32),
+            block_align,
+            bits_per_raw_sample: bits_per_sample,
+            extra_info_size: 0,
+            extended_info: None,
+        }
+    }
+
+    /// An empty fmt chunk with an unknown format, standing in for a missing
+    /// one so that no samples can be read.
+    #[cfg(feature = "std")]
+    fn placeholder() -> FmtChunk {
+        FmtChunk {
+            id: FourCC::Fmt,
+            data_size: 0,
+            format: Format::Other(0),
+            num_channels: 0,
+            sample_rate: 0,
+            byte_rate: 0,
+            block_align: 0,
+            bits_per_raw_sample: 0,
+            extra_info_size: 0,
+            extended_info: None,
+        }
+    }
+
+    /// Resolves the format and bit depth of extensible files from their
+    /// extended info.
+    pub fn audio_spec(&self) -> AudioSpec {
+        let bits_per_sample = match &self.extended_info {
+            Some(extended) if extended.bits_per_coded_sample != 0 => extended.bits_per_coded_sample,
+            _ => self.bits_per_raw_sample,
+        };
+
+        AudioSpec {
+            format: self.effective_format(),
+            bits_per_sample,
+            sample_rate: self.sample_rate,
+            channels: self.num_channels,
+        }
+    }
+
+    /// Returns the format of the samples, taking the format tag of
+    /// extensible files from the first two bytes of their sub-format GUID.
+    pub fn effective_format(&self) -> Format {
+        match (self.format, &self.extended_info) {
+            (Format::ExtendedWave, Some(extended)) => {
+                Format::from((extended.sub_format & 0xffff) as u16)
+            }
+            (format, _) => format,
+        }
+    }
+
+    #[cfg(feature = "std")]
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.num_channels == 0 || !self.block_align.is_multiple_of(self.num_channels) {
+            return Err(Error::InvalidChannelCount {
+                num_channels: self.num_channels,
+                block_align: self.block_align,
+            });
+        }
+
+        match self.effective_format() {
+            Format::UncompressedPCM
+            | Format::IeeeFloatingPoint
+            | Format::G711ALaw
+            | Format::G711ULaw => {}
+            _ => return Ok(()),
+        }
+
+        let bytes_per_sample = (self.bits_per_raw_sample as u32).div_ceil(8);
+
+        let block_align = self.num_channels as u32 * bytes_per_sample;
+        if self.block_align as u32 != block_align {
+            return Err(Error::InconsistentFmt {
+                field: "block_align",
+                expected: block_align,
+                found: self.block_align as u32,
+            });
+        }
+
+        let byte_rate = self.sample_rate.wrapping_mul(block_align);
+        if self.byte_rate != byte_rate {
+            return Err(Error::InconsistentFmt {
+                field: "byte_rate",
+                expected: byte_rate,
+                found: self.byte_rate,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Returns the number of bytes per sample, failing if the bit depth is
+    /// zero or not a whole number of bytes.
+    #[cfg(feature = "std")]
+    pub fn bytes_per_sample(&self) -> Result<u16, Error> {
+        let bits_per_sample = self.bits_per_raw_sample;
+
+        if bits_per_sample == 0 || !bits_per_sample.is_multiple_of(8) {
+            return Err(Error::UnsupportedBitDepth(bits_per_sample));
+        }
+
+        Ok(bits_per_sample / 8)
+    }
+
+    pub fn channel_mask_matches(&self) -> bool {
+        match &self.extended_info {
+            Some(extended) if extended.channel_mask != 0 => {
+                extended.speaker_positions().len() == self.num_channels as usize
+            }
+            _ => true,
+        }
+    }
+
+    pub fn is_compatible_with(&self, other: &FmtChunk) -> bool {
+        let extended_compatible = match (&self.extended_info, &other.extended_info) {
+            (Some(a), Some(b)) => {
+                a.bits_per_coded_sample == b.bits_per_coded_sample
+                    && a.channel_mask == b.channel_mask
+                    && a.sub_format == b.sub_format
+            }
+            (None, None) => true,
+            _ => false,
+        };
+
+        self.format == other.format
+            && self.num_channels == other.num_channels
+            && self.sample_rate == other.sample_rate
+            && self.byte_rate == other.byte_rate
+            && self.block_align == other.block_align
+            && self.bits_per_raw_sample == other.bits_per_raw_sample
+            && extended_compatible
+    }
+}
+
+/// Samples of any supported format, as read by [`RiffWaveReader::read_all`].
+/// 24-bit samples are sign extended to `i32`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Samples {
+    U8(Vec<u8>),
+    I16(Vec<i16>),
+    I24(Vec<i32>),
+    I32(Vec<i32>),
+    F32(Vec<f32>),
+    F64(Vec<f64>),
+}
+
+/// The sample format of a file, as resolved by [`FmtChunk::audio_spec`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct AudioSpec {
+    pub format: Format,
+    /// The valid bits of each sample, which may be fewer than the container
+    /// size for extensible files.
+    pub bits_per_sample: u16,
+    pub sample_rate: u32,
+    pub channels: u16,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExtendedInfo {
+    pub bits_per_coded_sample: u16,
+    pub channel_mask: u32,
+    /// The sub-format GUID in its little endian riff layout, whose low 16
+    /// bits hold the format tag. GUIDs read from RIFX files are converted to
+    /// this layout.
+    pub sub_format: u128,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::bytes"))]
+    pub remaining_data: Vec<u8>,
+}
+
+impl ExtendedInfo {
+    pub fn speaker_positions(&self) -> Vec<SpeakerPosition> {
+        SpeakerPosition::ALL
+            .iter()
+            .copied()
+            .filter(|position| self.channel_mask & position.mask() != 0)
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FactChunk {
+    pub id: FourCC,
+    pub data_size: u32,
+    pub sample_length: u32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::bytes"))]
+    pub remaining_data: Vec<u8>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ds64Chunk {
+    pub id: FourCC,
+    pub data_size: u32,
+    pub riff_size: u64,
+    pub data_chunk_size: u64,
+    pub sample_count: u64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::bytes"))]
+    pub remaining_data: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OtherChunk {
+    pub id: FourCC,
+    pub data_size: u32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::bytes"))]
+    pub data: Vec<u8>,
+}
+
+impl OtherChunk {
+    /// Returns the CRC-32 of the chunk data, excluding the header and pad
+    /// byte.
+    pub fn crc32(&self) -> u32 {
+        crc::crc32(0, &self.data)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DataChunk {
+    pub id: FourCC,
+    pub data_size: u64,
+    pub pad_byte: u8,
+    /// Set when the data size was declared as `0xFFFFFFFF` or 0, as written
+    /// by streaming encoders that don't know the final size. The data is then
+    /// read to the end of the riff chunk, or of the stream if the riff size is
+    /// unset too, and `data_size` holds the measured size. A size of 0
+    /// followed by another chunk is taken as an empty data chunk instead.
+    pub is_unsized: bool,
+}
+
+impl DataChunk {
+    fn from_header(header: &ChunkHeader) -> DataChunk {
+        let data_size = header.data_size as u64;
+
+        DataChunk {
+            id: header.id.clone(),
+            data_size,
+            pad_byte: (data_size % 2) as u8,
+            is_unsized: data_size == 0xffff_ffff || data_size == 0,
+        }
+    }
+
+    /// Stands in for the data chunk of a file holding its waveform in a
+    /// `wavl` list.
+    #[cfg(feature = "std")]
+    fn empty() -> DataChunk {
+        DataChunk {
+            id: FourCC::Data,
+            data_size: 0,
+            pad_byte: 0,
+            is_unsized: false,
+        }
+    }
+
+    /// Returns the number of whole frames, or 0 if `block_align` is 0.
+    pub fn num_frames(&self, block_align: u16) -> u64 {
+        self.data_size.checked_div(block_align as u64).unwrap_or(0)
+    }
+
+    /// Returns the number of samples across all channels of the whole frames.
+    pub fn num_samples(&self, block_align: u16, num_channels: u16) -> u64 {
+        self.num_frames(block_align)
+            .saturating_mul(num_channels as u64)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum FourCC {
+    Riff,
+    Rifx,
+    Rf64,
+    Bw64,
+    Ds64,
+    Fmt,
+    Data,
+    Wave,
+    Fact,
+    Other(String),
+}
+
+impl FourCC {
+    pub fn as_bytes(&self) -> [u8; 4] {
+        match self {
+            FourCC::Riff => *b"RIFF",
+            FourCC::Rifx => *b"RIFX",
+            FourCC::Rf64 => *b"RF64",
+            FourCC::Bw64 => *b"BW64",
+            FourCC::Ds64 => *b"ds64",
+            FourCC::Fmt => *b"fmt ",
+            FourCC::Data => *b"data",
+            FourCC::Wave => *b"WAVE",
+            FourCC::Fact => *b"fact",
+            FourCC::Other(id) => {
+                let mut bytes = [b' '; 4];
+                for (byte, id_byte) in bytes.iter_mut().zip(id.bytes()) {
+                    *byte = id_byte;
+                }
+                bytes
+            }
+        }
+    }
+
+    /// Converts an id of exactly 4 bytes, failing for slices of any other
+    /// length. This isn't a `TryFrom<&[u8]>` impl because the infallible
+    /// `From<&[u8]>` already provides one that never fails.
+    pub fn try_from_slice(data: &[u8]) -> Result<FourCC, TryFromSliceError> {
+        <[u8; 4]>::try_from(data).map(FourCC::from)
+    }
+}
+
+impl core::fmt::Display for FourCC {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            FourCC::Other(id) => write!(f, "{}", id),
+            _ => write!(f, "{}", String::from_utf8_lossy(&self.as_bytes())),
+        }
+    }
+}
+
+/// Ids of any length other than 4 bytes are kept as `FourCC::Other`. Use
+/// [`FourCC::try_from_slice`] to reject slices of the wrong length.
+impl From<&[u8]> for FourCC {
+    #[allow(clippy::unreadable_literal)]
+    fn from(data: &[u8]) -> Self {
+        match data {
+            b"RIFF" => FourCC::Riff,
+            b"RIFX" => FourCC::Rifx,
+            b"RF64" => FourCC::Rf64,
+            b"BW64" => FourCC::Bw64,
+            b"ds64" => FourCC::Ds64,
+            b"WAVE" => FourCC::Wave,
+            b"fmt " => FourCC::Fmt,
+            b"data" => FourCC::Data,
+            b"Data" => FourCC::Data,
+            b"fact" => FourCC::Fact,
+            _ => FourCC::Other(String::from_utf8_lossy(data).into_owned()),
+        }
+    }
+}
+
+impl From<[u8; 4]> for FourCC {
+    fn from(data: [u8; 4]) -> Self {
+        FourCC::from(&data[..])
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Format {
+    UncompressedPCM,
+    IeeeFloatingPoint,
+    G711ALaw,
+    G711ULaw,
+    ExtendedWave,
+    Other(u16),
+}
+
+impl Format {
+    /// Returns the numeric format tag, the inverse of `Format::from(u16)`.
+    pub fn as_tag(&self) -> u16 {
+        u16::from(*self)
+    }
+}
+
+impl From<u16> for Format {
+    fn from(format: u16) -> Self {
+        match format {
+            1 => Format::UncompressedPCM,
+            3 => Format::IeeeFloatingPoint,
+            6 => Format::G711ALaw,
+            7 => Format::G711ULaw,
+            65534 => Format::ExtendedWave,
+            _ => Format::Other(format),
+        }
+    }
+}
+
+impl core::fmt::Display for Format {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let name = match self {
+            Format::UncompressedPCM => "PCM",
+            Format::IeeeFloatingPoint => "IEEE Float",
+            Format::G711ALaw => "G.711 A-law",
+            Format::G711ULaw => "G.711 mu-law",
+            Format::ExtendedWave => "Extensible",
+            Format::Other(0x0002) => "Microsoft ADPCM",
+            Format::Other(0x0011) => "IMA ADPCM",
+            Format::Other(0x0031) => "GSM 6.10",
+            Format::Other(0x0050) => "MPEG",
+            Format::Other(0x0055) => "MPEG Layer 3",
+            Format::Other(0x0092) => "Dolby AC3 SPDIF",
+            Format::Other(0x00ff) => "AAC",
+            Format::Other(0x2000) => "Dolby AC3",
+            Format::Other(0xf1ac) => "FLAC",
+            Format::Other(format) => return write!(f, "Unknown ({:#06x})", format),
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+impl From<Format> for u16 {
+    fn from(format: Format) -> Self {
+        match format {
+            Format::UncompressedPCM => 1,
+            Format::IeeeFloatingPoint => 3,
+            Format::G711ALaw => 6,
+            Format::G711ULaw => 7,
+            Format::ExtendedWave => 65534,
+            Format::Other(format) => format,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Read + Seek> std::fmt::Display for RiffWaveReader<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let size = self.riff_chunk.file_size;
+        let format = self.fmt_chunk.format;
+        let num_channels = self.fmt_chunk.num_channels;
+        let sample_rate = self.fmt_chunk.sample_rate;
+        let byte_rate = self.fmt_chunk.byte_rate;
+        let block_align = self.fmt_chunk.block_align;
+        let bits_per_sample = self.fmt_chunk.bits_per_raw_sample;
+        let extra_info_size = self.fmt_chunk.extra_info_size;
+
+        let extended = if let Some(extended) = &self.fmt_chunk.extended_info {
+            format!(
+                "\n----- Extended -----
+Bits per Coded:  {}
+Channel Mask:    {:#018b}
+Sub Format:      {:x}
+Remaining Data:  {:x?}",
+                extended.bits_per_coded_sample,
+                extended.channel_mask,
+                extended.sub_format,
+                extended.remaining_data,
+            )
+        } else {
+            String::from("")
+        };
+
+        let fact = if let Some(fact) = &self.fact_chunk {
+            format!(
+                "\n------- Fact -------
+Fact Length:     {}
+Sample Length:   {}
 Remaining Data:  {:x?}",
                 fact.data_size, fact.sample_length, fact.remaining_data,
             )
-        } else {
-            String::from("")
+        } else {
+            String::from("")
+        };
+
+        let other_chunks = {
+            let chunk_ids = self
+                .other_chunks
+                .iter()
+                .map(|c| c.id.to_string())
+                .collect::<Vec<_>>();
+
+            if chunk_ids.is_empty() {
+                String::from("")
+            } else {
+                format!(
+                    "\n--- Other Chunks ---
+Chunk Ids:       {:?}",
+                    chunk_ids
+                )
+            }
+        };
+
+        let data = format!(
+            "\n------- Data -------
+Data Length:     {}
+Padding Byte:    {}",
+            self.data_chunk.data_size, self.data_chunk.pad_byte
+        );
+
+        write!(
+            f,
+            "------ Header ------
+Size:            {}
+Format:          {}
+Channels:        {}
+Sample Rate:     {}
+Byte Rate:       {}
+Block Align:     {}
+Bits per Raw:    {}
+Extra Info:      {}{}{}{}{}",
+            size,
+            format,
+            num_channels,
+            sample_rate,
+            byte_rate,
+            block_align,
+            bits_per_sample,
+            extra_info_size,
+            extended,
+            fact,
+            other_chunks,
+            data
+        )
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::fixture::{self, pcm16, temp_path, WavBuilder};
+
+    #[test]
+    fn fact_chunk_smaller_than_sample_length_is_invalid() {
+        let bytes = WavBuilder::new()
+            .fmt(1, 8000, 16)
+            .chunk(b"fact", &[0, 0])
+            .data(&[0, 0])
+            .build();
+
+        let result = RiffWaveReader::from_bytes(bytes);
+
+        assert!(matches!(
+            result,
+            Err(Error::Parse {
+                offset: 36,
+                kind: ParseKind::InvalidFactChunk
+            })
+        ));
+    }
+
+    #[test]
+    fn missing_data_chunk_is_reported_at_end_of_file() {
+        let bytes = WavBuilder::new().fmt(1, 8000, 16).build();
+
+        let result = RiffWaveReader::from_bytes(bytes);
+
+        assert!(matches!(
+            result,
+            Err(Error::Parse {
+                offset: 36,
+                kind: ParseKind::MissingDataChunk
+            })
+        ));
+    }
+
+    #[test]
+    fn data_stops_before_trailing_chunks() {
+        let mut reader = WavBuilder::new()
+            .fmt(1, 8000, 8)
+            .data(&[1, 2, 3])
+            .chunk(b"LIST", b"INFOtest")
+            .reader();
+
+        let data = reader.data().unwrap().collect::<Vec<_>>();
+
+        assert_eq!(reader.data_chunk.data_size, 3);
+        assert_eq!(data, [1, 2, 3]);
+    }
+
+    #[test]
+    fn fourcc_from_invalid_utf8_is_decoded_lossily() {
+        let fourcc = FourCC::from([0xff, 0x00, b'x', b'y']);
+
+        assert_eq!(fourcc, FourCC::Other(String::from("\u{fffd}\0xy")));
+    }
+
+    #[test]
+    fn odd_sized_chunks_are_followed_by_a_pad_byte() {
+        let reader = WavBuilder::new()
+            .fmt(1, 8000, 8)
+            .chunk(b"abcd", &[1])
+            .chunk(b"efgh", &[1, 2, 3])
+            .data(&[0, 0])
+            .reader();
+
+        let ids = reader
+            .other_chunks
+            .iter()
+            .map(|chunk| chunk.id.to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(ids, ["abcd", "efgh"]);
+        assert_eq!(reader.other_chunks[1].data, [1, 2, 3]);
+        assert_eq!(reader.data_chunk.id, FourCC::Data);
+        assert_eq!(reader.data_chunk.data_size, 2);
+    }
+
+    #[test]
+    fn samples_i16_reads_little_endian_samples() {
+        let samples = [0, 1, -1, i16::MAX, i16::MIN];
+        let mut reader = WavBuilder::new()
+            .fmt(1, 8000, 16)
+            .data(&pcm16(Endianness::Little, &samples))
+            .reader();
+
+        assert_eq!(reader.samples_i16().unwrap().collect::<Vec<_>>(), samples);
+    }
+
+    #[test]
+    fn samples_i16_rejects_other_bit_depths() {
+        let mut reader = WavBuilder::new().fmt(1, 8000, 8).data(&[0, 0]).reader();
+
+        assert!(matches!(
+            reader.samples_i16(),
+            Err(Error::UnsupportedBitDepth(8))
+        ));
+    }
+
+    #[test]
+    fn samples_i16_rejects_an_incomplete_sample() {
+        let mut reader = WavBuilder::new().fmt(1, 8000, 16).data(&[0, 0, 1]).reader();
+
+        assert!(matches!(reader.samples_i16(), Err(Error::IncompleteSample)));
+    }
+
+    #[test]
+    fn num_frames_and_duration_of_stereo_file() {
+        // 1.5 seconds of 44100 Hz stereo 16-bit audio
+        let reader = WavBuilder::new()
+            .fmt(2, 44100, 16)
+            .data(&vec![0; 66150 * 4])
+            .reader();
+
+        assert_eq!(reader.num_frames(), 66150);
+        assert_eq!(reader.duration(), Duration::from_millis(1500));
+    }
+
+    #[test]
+    fn duration_of_zero_sample_rate_is_zero() {
+        let reader = WavBuilder::new().fmt(2, 0, 16).data(&[0; 8]).reader();
+
+        assert_eq!(reader.num_frames(), 2);
+        assert_eq!(reader.duration(), Duration::ZERO);
+    }
+
+    #[test]
+    fn num_frames_of_zero_block_align_is_zero() {
+        let reader = WavBuilder::new().fmt(0, 8000, 16).data(&[0; 8]).reader();
+
+        assert_eq!(reader.num_frames(), 0);
+        assert_eq!(reader.duration(), Duration::ZERO);
+    }
+
+    #[test]
+    fn data_reader_streams_exactly_the_data() {
+        let data = (0..10_001).map(|i| i as u8).collect::<Vec<_>>();
+        let mut reader = WavBuilder::new()
+            .fmt(1, 8000, 8)
+            .data(&data)
+            .chunk(b"LIST", b"INFO")
+            .reader();
+
+        let mut streamed = vec![];
+        let mut buf = [0; 4096];
+        let mut data_reader = reader.data_reader().unwrap();
+        loop {
+            let len = data_reader.read(&mut buf).unwrap();
+            if len == 0 {
+                break;
+            }
+
+            streamed.extend_from_slice(&buf[..len]);
+        }
+        drop(data_reader);
+
+        assert_eq!(streamed.len() as u64, reader.data_chunk.data_size);
+        assert_eq!(streamed, data);
+    }
+
+    #[test]
+    fn seek_to_frame_positions_on_the_frame() {
+        let samples = (0..400).collect::<Vec<i16>>();
+        let mut reader = WavBuilder::new()
+            .fmt(2, 8000, 16)
+            .data(&pcm16(Endianness::Little, &samples))
+            .reader();
+
+        reader.seek_to_frame(100).unwrap();
+        let mut buf = [0; 2];
+        reader.read_frames(&mut buf).unwrap();
+
+        assert_eq!(buf, [200, 201]);
+    }
+
+    #[test]
+    fn seek_to_frame_past_the_end_is_out_of_range() {
+        let mut reader = WavBuilder::new().fmt(2, 8000, 16).data(&[0; 8]).reader();
+
+        assert!(reader.seek_to_frame(2).is_ok());
+        assert!(matches!(
+            reader.seek_to_frame(3),
+            Err(Error::SeekOutOfRange)
+        ));
+    }
+
+    #[test]
+    fn effective_format_of_extensible_pcm() {
+        let reader = WavBuilder::new()
+            .fmt_extensible(1, 2, 48000, 24, 0x3)
+            .data(&[0; 6])
+            .reader();
+
+        assert_eq!(reader.fmt_chunk.format, Format::ExtendedWave);
+        assert_eq!(reader.fmt_chunk.effective_format(), Format::UncompressedPCM);
+    }
+
+    #[test]
+    fn effective_format_of_rifx_extensible_pcm() {
+        let reader = WavBuilder::rifx()
+            .fmt_extensible(1, 2, 48000, 24, 0x3)
+            .data(&[0; 6])
+            .reader();
+
+        assert_eq!(reader.fmt_chunk.format, Format::ExtendedWave);
+        assert_eq!(reader.fmt_chunk.effective_format(), Format::UncompressedPCM);
+        assert_eq!(reader.audio_spec().bits_per_sample, 24);
+    }
+
+    #[test]
+    fn effective_format_of_extensible_float() {
+        let reader = WavBuilder::new()
+            .fmt_extensible(3, 1, 48000, 32, 0x4)
+            .data(&[0; 4])
+            .reader();
+
+        assert_eq!(
+            reader.fmt_chunk.effective_format(),
+            Format::IeeeFloatingPoint
+        );
+    }
+
+    #[test]
+    fn effective_format_of_plain_fmt_is_its_format() {
+        let fmt_chunk = FmtChunk::pcm(1, 8000, 16);
+
+        assert_eq!(fmt_chunk.effective_format(), Format::UncompressedPCM);
+    }
+
+    #[test]
+    fn samples_i24_sign_extends() {
+        let mut reader = WavBuilder::new()
+            .fmt(1, 48000, 24)
+            .data(&[
+                0xff, 0xff, 0xff, // -1
+                0x01, 0x00, 0x00, // 1
+                0xff, 0xff, 0x7f, // max
+                0x00, 0x00, 0x80, // min
+            ])
+            .reader();
+
+        assert_eq!(
+            reader.samples_i24().unwrap().collect::<Vec<_>>(),
+            [-1, 1, 8_388_607, -8_388_608]
+        );
+    }
+
+    #[test]
+    fn samples_i24_rejects_inconsistent_block_align() {
+        let bytes = WavBuilder::new()
+            .chunk(b"fmt ", &{
+                let mut body = fixture::fmt_body(Endianness::Little, 1, 1, 48000, 24);
+                // A block align of 4 holds 24-bit samples in 32-bit containers
+                body[12] = 4;
+                body
+            })
+            .data(&[0; 4])
+            .build();
+        let mut reader = RiffWaveReader::from_bytes(bytes).unwrap();
+
+        assert!(matches!(
+            reader.samples_i24(),
+            Err(Error::UnsupportedBitDepth(24))
+        ));
+    }
+
+    #[test]
+    fn samples_f32_reads_float_data() {
+        let samples = [0.0f32, 0.5, -0.25, 1.0];
+        let data = samples
+            .iter()
+            .flat_map(|sample| sample.to_le_bytes())
+            .collect::<Vec<_>>();
+        let mut reader = WavBuilder::new()
+            .fmt_format(3, 1, 48000, 32)
+            .data(&data)
+            .chunk(b"LIST", b"INFO")
+            .reader();
+
+        let decoded = reader.samples_f32().unwrap().collect::<Vec<_>>();
+
+        assert_eq!(decoded.len(), samples.len());
+        for (decoded, sample) in decoded.iter().zip(&samples) {
+            assert!((decoded - sample).abs() < f32::EPSILON);
+        }
+    }
+
+    #[test]
+    fn samples_f64_reads_float_data() {
+        let samples = [0.1f64, -0.7];
+        let data = samples
+            .iter()
+            .flat_map(|sample| sample.to_le_bytes())
+            .collect::<Vec<_>>();
+        let mut reader = WavBuilder::new()
+            .fmt_format(3, 2, 48000, 64)
+            .data(&data)
+            .reader();
+
+        let decoded = reader.samples_f64().unwrap().collect::<Vec<_>>();
+
+        assert_eq!(decoded.len(), samples.len());
+        for (decoded, sample) in decoded.iter().zip(&samples) {
+            assert!((decoded - sample).abs() < f64::EPSILON);
+        }
+    }
+
+    #[test]
+    fn samples_f32_rejects_pcm_and_64_bit_data() {
+        let mut pcm = WavBuilder::new().fmt(1, 48000, 32).data(&[0; 4]).reader();
+        let mut double = WavBuilder::new()
+            .fmt_format(3, 1, 48000, 64)
+            .data(&[0; 8])
+            .reader();
+
+        assert!(matches!(
+            pcm.samples_f32(),
+            Err(Error::UnsupportedFormat(Format::UncompressedPCM))
+        ));
+        assert!(matches!(
+            double.samples_f32(),
+            Err(Error::UnsupportedBitDepth(64))
+        ));
+    }
+
+    #[test]
+    fn frames_i16_splits_channels() {
+        let mut reader = WavBuilder::new()
+            .fmt(2, 8000, 16)
+            .data(&pcm16(Endianness::Little, &[1, -1, 2, -2, 3, -3]))
+            .reader();
+
+        let frames = reader.frames_i16().unwrap().collect::<Vec<_>>();
+
+        assert_eq!(frames, [[1, -1], [2, -2], [3, -3]]);
+    }
+
+    #[test]
+    fn frames_i16_rejects_an_incomplete_frame() {
+        let mut reader = WavBuilder::new()
+            .fmt(2, 8000, 16)
+            .data(&pcm16(Endianness::Little, &[1, -1, 2]))
+            .reader();
+
+        assert!(matches!(reader.frames_i16(), Err(Error::IncompleteFrame)));
+    }
+
+    #[test]
+    fn consistent_fmt_chunk_is_valid() {
+        assert!(FmtChunk::pcm(2, 44100, 16).validate().is_ok());
+        assert!(FmtChunk::pcm(6, 48000, 24).validate().is_ok());
+    }
+
+    #[test]
+    fn inconsistent_byte_rate_is_reported() {
+        let fmt_chunk = FmtChunk {
+            byte_rate: 44100,
+            ..FmtChunk::pcm(2, 44100, 16)
+        };
+
+        assert!(matches!(
+            fmt_chunk.validate(),
+            Err(Error::InconsistentFmt {
+                field: "byte_rate",
+                expected: 176_400,
+                found: 44100
+            })
+        ));
+    }
+
+    #[test]
+    fn inconsistent_block_align_is_reported() {
+        let fmt_chunk = FmtChunk {
+            block_align: 2,
+            ..FmtChunk::pcm(2, 44100, 16)
+        };
+
+        assert!(matches!(
+            fmt_chunk.validate(),
+            Err(Error::InconsistentFmt {
+                field: "block_align",
+                expected: 4,
+                found: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn strict_parse_rejects_inconsistent_fmt_chunk() {
+        let mut body = fixture::fmt_body(Endianness::Little, 1, 2, 44100, 16);
+        // byte_rate
+        body[8..12].copy_from_slice(&[0; 4]);
+        let bytes = WavBuilder::new()
+            .chunk(b"fmt ", &body)
+            .data(&[0; 4])
+            .build();
+
+        assert!(RiffWaveReader::new(Cursor::new(bytes.clone())).is_ok());
+        assert!(matches!(
+            RiffWaveReader::new_strict(Cursor::new(bytes)),
+            Err(Error::InconsistentFmt {
+                field: "byte_rate",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn rifx_fmt_chunk_matches_riff_equivalent() {
+        let samples = [0x1234, -2];
+        let mut riff = WavBuilder::new()
+            .fmt(2, 44100, 16)
+            .data(&pcm16(Endianness::Little, &samples))
+            .reader();
+        let mut rifx = WavBuilder::rifx()
+            .fmt(2, 44100, 16)
+            .data(&pcm16(Endianness::Big, &samples))
+            .reader();
+
+        assert_eq!(rifx.riff_chunk.id, FourCC::Rifx);
+        assert_eq!(rifx.endianness, Endianness::Big);
+        assert_eq!(rifx.riff_chunk.file_size, riff.riff_chunk.file_size);
+        assert_eq!(rifx.fmt_chunk, riff.fmt_chunk);
+        assert_eq!(rifx.data_chunk, riff.data_chunk);
+        assert_eq!(
+            rifx.samples_i16().unwrap().collect::<Vec<_>>(),
+            riff.samples_i16().unwrap().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn rifx_extensible_fmt_chunk_matches_riff_equivalent() {
+        let riff = WavBuilder::new()
+            .fmt_extensible(1, 2, 48000, 24, 0x3)
+            .data(&[0; 6])
+            .reader();
+        let rifx = WavBuilder::rifx()
+            .fmt_extensible(1, 2, 48000, 24, 0x3)
+            .data(&[0; 6])
+            .reader();
+
+        assert_eq!(rifx.fmt_chunk, riff.fmt_chunk);
+        assert_eq!(
+            rifx.fmt_chunk.extended_info.unwrap().sub_format,
+            0x719b_3800_aa00_0080_0010_0000_0000_0001
+        );
+    }
+
+    #[test]
+    fn rf64_data_size_is_read_from_ds64() {
+        let data_size = 5 * 1024 * 1024 * 1024u64;
+
+        let mut ds64 = vec![];
+        ds64.extend_from_slice(&(data_size + 72).to_le_bytes());
+        ds64.extend_from_slice(&data_size.to_le_bytes());
+        ds64.extend_from_slice(&(data_size / 4).to_le_bytes());
+        ds64.extend_from_slice(&0u32.to_le_bytes());
+
+        let reader = WavBuilder::with_id(*b"RF64")
+            .chunk(b"ds64", &ds64)
+            .fmt(2, 48000, 16)
+            .chunk_sized(b"data", 0xffff_ffff, &[0; 8])
+            .reader();
+
+        assert_eq!(reader.riff_chunk.id, FourCC::Rf64);
+        assert_eq!(
+            reader.ds64_chunk.as_ref().unwrap().riff_size,
+            data_size + 72
+        );
+        assert_eq!(reader.data_chunk.data_size, data_size);
+        assert!(!reader.data_chunk.is_unsized);
+        assert_eq!(reader.num_frames(), data_size / 4);
+        assert_eq!(reader.duration().as_secs(), data_size / 4 / 48000);
+        assert!(reader.is_truncated());
+    }
+
+    #[test]
+    fn rf64_without_ds64_is_invalid() {
+        let bytes = WavBuilder::with_id(*b"BW64")
+            .fmt(1, 8000, 8)
+            .data(&[0])
+            .build();
+
+        assert!(matches!(
+            RiffWaveReader::from_bytes(bytes),
+            Err(Error::Parse {
+                offset: 12,
+                kind: ParseKind::InvalidDs64Chunk
+            })
+        ));
+    }
+
+    #[test]
+    fn get_chunk_finds_chunk_by_id() {
+        let reader = WavBuilder::new()
+            .fmt(1, 8000, 8)
+            .chunk(b"cue ", &[0; 4])
+            .chunk(b"LIST", b"INFO")
+            .chunk(b"LIST", b"adtl")
+            .data(&[0])
+            .reader();
+
+        let cue = reader.get_chunk("cue ").unwrap();
+        assert_eq!(cue.id, FourCC::Other(String::from("cue ")));
+        assert_eq!(cue.data, [0; 4]);
+
+        assert!(reader.get_chunk("cue").is_none());
+        assert!(reader.get_chunk("smpl").is_none());
+
+        let lists = reader
+            .get_chunks("LIST")
+            .map(|chunk| chunk.data.as_slice())
+            .collect::<Vec<_>>();
+        assert_eq!(lists, [b"INFO", b"adtl"]);
+    }
+
+    #[test]
+    fn fourcc_round_trips_through_bytes() {
+        let ids = [
+            FourCC::Riff,
+            FourCC::Rifx,
+            FourCC::Rf64,
+            FourCC::Bw64,
+            FourCC::Ds64,
+            FourCC::Fmt,
+            FourCC::Data,
+            FourCC::Wave,
+            FourCC::Fact,
+            FourCC::Other(String::from("LIST")),
+        ];
+
+        for id in &ids {
+            assert_eq!(&FourCC::from(id.as_bytes()), id);
+        }
+    }
+
+    #[test]
+    fn fourcc_displays_its_code() {
+        assert_eq!(FourCC::Fmt.to_string(), "fmt ");
+        assert_eq!(FourCC::Riff.to_string(), "RIFF");
+        assert_eq!(FourCC::Other(String::from("cue ")).to_string(), "cue ");
+    }
+
+    #[test]
+    fn channel_mask_decodes_5_1_layout() {
+        let reader = WavBuilder::new()
+            .fmt_extensible(1, 6, 48000, 16, 0x3f)
+            .data(&[0; 12])
+            .reader();
+        let extended = reader.fmt_chunk.extended_info.as_ref().unwrap();
+
+        assert_eq!(
+            extended.speaker_positions(),
+            [
+                SpeakerPosition::FrontLeft,
+                SpeakerPosition::FrontRight,
+                SpeakerPosition::FrontCenter,
+                SpeakerPosition::LowFrequency,
+                SpeakerPosition::BackLeft,
+                SpeakerPosition::BackRight,
+            ]
+        );
+        assert!(reader.fmt_chunk.channel_mask_matches());
+    }
+
+    #[test]
+    fn channel_mask_with_wrong_count_does_not_match() {
+        let reader = WavBuilder::new()
+            .fmt_extensible(1, 2, 48000, 16, 0x3f)
+            .data(&[0; 4])
+            .reader();
+
+        assert!(!reader.fmt_chunk.channel_mask_matches());
+    }
+
+    #[test]
+    fn malformed_metadata_chunk_is_kept_as_other_chunk() {
+        let bytes = WavBuilder::new()
+            .fmt(1, 8000, 8)
+            .chunk(b"bext", &[0; 10])
+            .data(&[0])
+            .build();
+
+        let reader = RiffWaveReader::new(Cursor::new(bytes.clone())).unwrap();
+
+        assert!(reader.bext.is_none());
+        assert_eq!(reader.get_chunk("bext").unwrap().data, [0; 10]);
+
+        assert!(matches!(
+            RiffWaveReader::new_strict(Cursor::new(bytes)),
+            Err(Error::Parse {
+                offset: 36,
+                kind: ParseKind::InvalidBextChunk
+            })
+        ));
+    }
+
+    #[test]
+    fn invalid_cue_chunk_leaves_cue_points_unset() {
+        let reader = WavBuilder::new()
+            .fmt(1, 8000, 8)
+            .chunk(b"cue ", &5u32.to_le_bytes())
+            .data(&[0])
+            .reader();
+
+        assert!(reader.cue_points.is_none());
+        assert!(reader.get_chunk("cue ").is_some());
+    }
+
+    #[test]
+    fn data_offset_points_at_first_sample() {
+        let bytes = WavBuilder::new()
+            .fmt(1, 8000, 8)
+            .chunk(b"LIST", b"INFO")
+            .data(&[0xaa, 0xbb])
+            .build();
+
+        let reader = RiffWaveReader::new(Cursor::new(bytes.clone())).unwrap();
+
+        // riff header, fmt chunk, LIST chunk and data chunk header
+        assert_eq!(reader.data_offset(), 12 + 24 + 12 + 8);
+        assert_eq!(bytes[reader.data_offset() as usize], 0xaa);
+    }
+
+    #[test]
+    fn open_reads_file_from_path() {
+        let path = temp_path("open.wav");
+        let bytes = WavBuilder::new().fmt(1, 8000, 8).data(&[1, 2]).build();
+        std::fs::write(&path, bytes).unwrap();
+
+        let result = RiffWaveReader::open(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        let mut reader = result.unwrap();
+        assert_eq!(reader.data().unwrap().collect::<Vec<_>>(), [1, 2]);
+    }
+
+    #[test]
+    fn open_missing_file_is_io_error() {
+        let result = RiffWaveReader::open(temp_path("missing.wav"));
+
+        assert!(matches!(
+            result,
+            Err(Error::IOError(e)) if e.kind() == io::ErrorKind::NotFound
+        ));
+    }
+
+    #[test]
+    fn from_bytes_parses_minimal_file() {
+        let mut bytes = b"RIFF".to_vec();
+        bytes.extend_from_slice(&38u32.to_le_bytes());
+        bytes.extend_from_slice(b"WAVEfmt ");
+        bytes.extend_from_slice(&16u32.to_le_bytes());
+        bytes.extend_from_slice(&[1, 0, 1, 0]);
+        bytes.extend_from_slice(&8000u32.to_le_bytes());
+        bytes.extend_from_slice(&16000u32.to_le_bytes());
+        bytes.extend_from_slice(&[2, 0, 16, 0]);
+        bytes.extend_from_slice(b"data");
+        bytes.extend_from_slice(&2u32.to_le_bytes());
+        bytes.extend_from_slice(&[0x34, 0x12]);
+
+        let mut reader = RiffWaveReader::from_bytes(bytes).unwrap();
+
+        assert_eq!(reader.fmt_chunk, FmtChunk::pcm(1, 8000, 16));
+        assert_eq!(reader.samples_i16().unwrap().collect::<Vec<_>>(), [0x1234]);
+    }
+
+    #[test]
+    fn fact_after_list_is_parsed() {
+        let reader = WavBuilder::new()
+            .fmt_format(6, 1, 8000, 8)
+            .chunk(b"LIST", b"INFO")
+            .chunk(b"fact", &4u32.to_le_bytes())
+            .data(&[0; 4])
+            .reader();
+
+        assert_eq!(reader.fact_chunk.unwrap().sample_length, 4);
+        assert_eq!(reader.other_chunks.len(), 1);
+        assert_eq!(reader.data_chunk.data_size, 4);
+    }
+
+    #[test]
+    fn list_before_fmt_is_parsed() {
+        let reader = WavBuilder::new()
+            .chunk(b"LIST", b"INFO")
+            .chunk(b"fact", &4u32.to_le_bytes())
+            .fmt_format(6, 1, 8000, 8)
+            .data(&[0; 4])
+            .reader();
+
+        assert_eq!(reader.fmt_chunk.format, Format::G711ALaw);
+        assert_eq!(reader.fact_chunk.unwrap().sample_length, 4);
+        assert_eq!(reader.other_chunks.len(), 1);
+    }
+
+    #[test]
+    fn parse_error_reports_offset_of_bad_chunk() {
+        let bytes = WavBuilder::new()
+            .chunk(b"LIST", b"INFO")
+            .chunk(b"fmt ", &[0; 10])
+            .data(&[0])
+            .build();
+
+        assert!(matches!(
+            RiffWaveReader::from_bytes(bytes),
+            Err(Error::Parse {
+                offset: 24,
+                kind: ParseKind::InvalidFmtChunk
+            })
+        ));
+    }
+
+    #[test]
+    fn parse_error_offset_is_relative_to_stream_start() {
+        let mut bytes = vec![0; 10];
+        bytes.extend(WavBuilder::with_id(*b"RIFZ").fmt(1, 8000, 8).build());
+        let mut cursor = Cursor::new(bytes);
+        cursor.set_position(10);
+
+        assert!(matches!(
+            RiffWaveReader::new(cursor),
+            Err(Error::Parse {
+                offset: 10,
+                kind: ParseKind::NotRiff
+            })
+        ));
+    }
+
+    #[test]
+    fn samples_decoded_i16_expands_g711_data() {
+        let mut alaw = WavBuilder::new()
+            .fmt_format(6, 1, 8000, 8)
+            .chunk(b"fact", &2u32.to_le_bytes())
+            .data(&[0xd5, 0x2a])
+            .reader();
+        let mut ulaw = WavBuilder::new()
+            .fmt_format(7, 1, 8000, 8)
+            .chunk(b"fact", &2u32.to_le_bytes())
+            .data(&[0xff, 0x80])
+            .reader();
+
+        assert_eq!(
+            alaw.samples_decoded_i16().unwrap().collect::<Vec<_>>(),
+            [8, -32256]
+        );
+        assert_eq!(
+            ulaw.samples_decoded_i16().unwrap().collect::<Vec<_>>(),
+            [0, 32124]
+        );
+    }
+
+    #[test]
+    fn samples_decoded_i16_passes_pcm_through_and_rejects_float() {
+        let mut pcm = WavBuilder::new()
+            .fmt(1, 8000, 16)
+            .data(&pcm16(Endianness::Little, &[-3, 3]))
+            .reader();
+        let mut float = WavBuilder::new()
+            .fmt_format(3, 1, 8000, 32)
+            .data(&[0; 4])
+            .reader();
+
+        assert_eq!(
+            pcm.samples_decoded_i16().unwrap().collect::<Vec<_>>(),
+            [-3, 3]
+        );
+        assert!(matches!(
+            float.samples_decoded_i16(),
+            Err(Error::UnsupportedFormat(Format::IeeeFloatingPoint))
+        ));
+    }
+
+    #[test]
+    fn samples_normalized_scales_8_bit_unsigned() {
+        let mut reader = WavBuilder::new()
+            .fmt(1, 8000, 8)
+            .data(&[0, 128, 255, 64])
+            .reader();
+
+        assert_eq!(
+            reader.samples_normalized().unwrap().collect::<Vec<_>>(),
+            [-1.0, 0.0, 127.0 / 128.0, -0.5]
+        );
+    }
+
+    #[test]
+    fn samples_normalized_scales_16_bit_signed() {
+        let mut reader = WavBuilder::new()
+            .fmt(1, 8000, 16)
+            .data(&pcm16(Endianness::Little, &[i16::MIN, 0, i16::MAX, 16384]))
+            .reader();
+
+        assert_eq!(
+            reader.samples_normalized().unwrap().collect::<Vec<_>>(),
+            [-1.0, 0.0, 32767.0 / 32768.0, 0.5]
+        );
+    }
+
+    #[test]
+    fn samples_normalized_scales_24_bit_signed() {
+        let mut reader = WavBuilder::new()
+            .fmt(1, 8000, 24)
+            .data(&[0x00, 0x00, 0x80, 0x00, 0x00, 0x40])
+            .reader();
+
+        assert_eq!(
+            reader.samples_normalized().unwrap().collect::<Vec<_>>(),
+            [-1.0, 0.5]
+        );
+    }
+
+    #[test]
+    fn verify_file_size_reports_short_file() {
+        let mut bytes = WavBuilder::new().fmt(1, 8000, 8).data(&[0; 10]).build();
+        bytes.truncate(bytes.len() - 4);
+        let mut reader = RiffWaveReader::from_bytes(bytes).unwrap();
+
+        assert!(matches!(
+            reader.verify_file_size(),
+            Err(Error::FileSizeMismatch {
+                declared: 54,
+                actual: 50
+            })
+        ));
+    }
+
+    #[test]
+    fn verify_file_size_accepts_matching_file() {
+        let mut reader = WavBuilder::new().fmt(1, 8000, 8).data(&[0; 10]).reader();
+        let position = reader.position().unwrap();
+
+        reader.verify_file_size().unwrap();
+        assert_eq!(reader.position().unwrap(), position);
+    }
+
+    #[test]
+    fn chunk_layout_lists_chunks_in_file_order() {
+        let reader = WavBuilder::new()
+            .fmt(1, 8000, 8)
+            .data(&[0; 3])
+            .chunk(b"LIST", b"INFO")
+            .chunk(b"id3 ", &[1])
+            .reader();
+
+        let layout = reader
+            .chunk_layout()
+            .iter()
+            .map(|chunk| (chunk.id.to_string(), chunk.offset, chunk.size))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            layout,
+            [
+                (String::from("fmt "), 12, 16),
+                (String::from("data"), 36, 3),
+                (String::from("LIST"), 48, 4),
+                (String::from("id3 "), 60, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn chunk_layout_stops_at_riff_end() {
+        let mut bytes = WavBuilder::new()
+            .fmt(1, 8000, 8)
+            .data(&[0; 2])
+            .chunk(b"LIST", b"INFO")
+            .build();
+        bytes.extend(WavBuilder::new().fmt(1, 8000, 8).data(&[0; 2]).build());
+
+        let reader = RiffWaveReader::from_bytes(bytes).unwrap();
+
+        let ids = reader
+            .chunk_layout()
+            .iter()
+            .map(|chunk| chunk.id.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, ["fmt ", "data", "LIST"]);
+    }
+
+    #[test]
+    fn skip_to_after_data_skips_the_pad_byte() {
+        let mut reader = WavBuilder::new()
+            .fmt(1, 8000, 8)
+            .data(&[1, 2, 3])
+            .chunk(b"id3 ", &[4, 5])
+            .reader();
+
+        reader.skip_to_after_data().unwrap();
+
+        let mut id = [0; 4];
+        reader.reader.read_exact(&mut id).unwrap();
+        assert_eq!(&id, b"id3 ");
+    }
+
+    #[test]
+    fn peak_chunk_before_fmt_is_parsed() {
+        let peak = [1u32, 0, 0.25f32.to_bits(), 10]
+            .iter()
+            .flat_map(|n| n.to_le_bytes())
+            .collect::<Vec<_>>();
+
+        let mut reader = WavBuilder::new()
+            .chunk(b"PEAK", &peak)
+            .fmt(1, 8000, 8)
+            .data(&[0; 2])
+            .reader();
+
+        let peak = reader.peak.as_ref().unwrap();
+        assert_eq!(peak.peaks.len(), 1);
+        assert_eq!(peak.peaks[0].value, 0.25);
+        assert_eq!(peak.peaks[0].position, 10);
+        assert!(reader.spec_violations().unwrap().is_empty());
+    }
+
+    #[test]
+    fn read_u24_reads_low_and_high_bytes() {
+        let mut reader = Cursor::new(vec![0x01, 0x00, 0x00, 0x00, 0x00, 0xff]);
+
+        assert_eq!(reader.read_u24(Endianness::Little).unwrap(), 1);
+        assert_eq!(reader.read_u24(Endianness::Little).unwrap(), 0xff_0000);
+
+        let mut reader = Cursor::new(vec![0x12, 0x34, 0x56]);
+        assert_eq!(reader.read_u24(Endianness::Big).unwrap(), 0x12_3456);
+    }
+
+    #[test]
+    fn read_u24_fails_on_a_partial_value() {
+        let mut reader = Cursor::new(vec![0x01, 0x02]);
+
+        assert!(reader.read_u24(Endianness::Little).is_err());
+    }
+
+    #[test]
+    fn sign_extend_i24_keeps_the_sign_bit() {
+        assert_eq!(sign_extend_i24(0x7f_ffff), 8_388_607);
+        assert_eq!(sign_extend_i24(0x80_0000), -8_388_608);
+        assert_eq!(sign_extend_i24(0xff_ffff), -1);
+        assert_eq!(sign_extend_i24(0x00_0001), 1);
+    }
+
+    #[test]
+    fn wrong_riff_size_is_only_rejected_in_strict_mode() {
+        let mut bytes = WavBuilder::new().fmt(1, 8000, 8).data(&[0; 2]).build();
+        bytes[4..8].copy_from_slice(&100u32.to_le_bytes());
+
+        assert!(RiffWaveReader::new(Cursor::new(bytes.clone())).is_ok());
+        assert!(matches!(
+            RiffWaveReader::new_strict(Cursor::new(bytes)),
+            Err(Error::FileSizeMismatch {
+                declared: 108,
+                actual: 46
+            })
+        ));
+    }
+
+    #[test]
+    fn missing_fact_chunk_is_only_rejected_in_strict_mode() {
+        let bytes = WavBuilder::new()
+            .fmt_format(3, 1, 8000, 32)
+            .data(&[0; 4])
+            .build();
+
+        assert!(RiffWaveReader::new(Cursor::new(bytes.clone())).is_ok());
+        assert!(matches!(
+            RiffWaveReader::new_strict(Cursor::new(bytes)),
+            Err(Error::Parse {
+                offset: 36,
+                kind: ParseKind::MissingFactChunk
+            })
+        ));
+    }
+
+    #[test]
+    fn well_formed_file_parses_in_strict_mode() {
+        let bytes = WavBuilder::new()
+            .fmt(2, 44100, 16)
+            .chunk(b"LIST", b"INFO")
+            .data(&[0; 8])
+            .build();
+
+        assert!(RiffWaveReader::new_strict(Cursor::new(bytes)).is_ok());
+    }
+
+    #[test]
+    fn padded_fmt_chunk_is_skipped_to_its_end() {
+        let mut body = fixture::fmt_body(Endianness::Little, 1, 2, 44100, 16);
+        body.resize(40, 0);
+
+        let reader = WavBuilder::new()
+            .chunk(b"fmt ", &body)
+            .chunk(b"LIST", b"INFO")
+            .data(&[1, 2, 3, 4])
+            .reader();
+
+        assert_eq!(reader.fmt_chunk.data_size, 40);
+        assert_eq!(reader.fmt_chunk.num_channels, 2);
+        assert_eq!(reader.get_chunk("LIST").unwrap().data, b"INFO");
+        assert_eq!(reader.data_chunk.data_size, 4);
+        assert_eq!(reader.data_offset(), 80);
+    }
+
+    #[test]
+    fn zero_channel_fmt_chunk_is_invalid() {
+        let fmt_chunk = FmtChunk {
+            num_channels: 0,
+            ..FmtChunk::pcm(1, 8000, 16)
+        };
+
+        assert!(matches!(
+            fmt_chunk.validate(),
+            Err(Error::InvalidChannelCount {
+                num_channels: 0,
+                block_align: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn block_align_not_divisible_by_channels_is_invalid() {
+        let fmt_chunk = FmtChunk {
+            block_align: 5,
+            ..FmtChunk::pcm(2, 8000, 16)
+        };
+
+        assert!(matches!(
+            fmt_chunk.validate(),
+            Err(Error::InvalidChannelCount {
+                num_channels: 2,
+                block_align: 5
+            })
+        ));
+    }
+
+    #[test]
+    fn zero_channel_file_is_rejected_in_strict_mode() {
+        let bytes = WavBuilder::new().fmt(0, 8000, 16).data(&[0; 2]).build();
+
+        assert!(matches!(
+            RiffWaveReader::new_strict(Cursor::new(bytes)),
+            Err(Error::InvalidChannelCount {
+                num_channels: 0,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn cue_label_is_read_from_adtl_list() {
+        let mut cue = 1u32.to_le_bytes().to_vec();
+        for n in [7u32, 100] {
+            cue.extend_from_slice(&n.to_le_bytes());
+        }
+        cue.extend_from_slice(b"data");
+        for n in [0u32, 0, 100] {
+            cue.extend_from_slice(&n.to_le_bytes());
+        }
+
+        let mut label = 7u32.to_le_bytes().to_vec();
+        label.extend_from_slice(b"Intro\0");
+        let adtl = fixture::list_data(Endianness::Little, b"adtl", &[(b"labl", &label)]);
+
+        let reader = WavBuilder::new()
+            .fmt(1, 8000, 8)
+            .chunk(b"cue ", &cue)
+            .chunk(b"LIST", &adtl)
+            .data(&[0; 200])
+            .reader();
+
+        let cue_points = reader.cue_points.as_ref().unwrap();
+        assert_eq!(reader.cue_label(&cue_points[0]), Some("Intro"));
+    }
+
+    #[test]
+    fn read_frames_fills_fixed_buffers_until_the_end_of_data() {
+        let samples = (0..1000).map(|n| n as i16 - 500).collect::<Vec<_>>();
+        let mut reader = WavBuilder::new()
+            .fmt(1, 8000, 16)
+            .data(&pcm16(Endianness::Little, &samples))
+            .chunk(b"LIST", b"INFO")
+            .reader();
+
+        let mut buf = [0; 256];
+        let mut read = vec![];
+        loop {
+            let len = reader.read_frames(&mut buf).unwrap();
+            if len == 0 {
+                break;
+            }
+            read.extend_from_slice(&buf[..len]);
+        }
+
+        assert_eq!(read.len(), 1000);
+        assert_eq!(read, samples);
+        assert_eq!(reader.read_frames(&mut buf).unwrap(), 0);
+    }
+
+    #[test]
+    fn read_frames_skips_a_trailing_partial_sample() {
+        let mut data = pcm16(Endianness::Little, &[1, 2]);
+        data.push(0x7f);
+        let mut reader = WavBuilder::new().fmt(1, 8000, 16).data(&data).reader();
+
+        let mut buf = [0; 4];
+        assert_eq!(reader.read_frames(&mut buf).unwrap(), 2);
+        assert_eq!(buf[..2], [1, 2]);
+        assert_eq!(reader.read_frames(&mut buf).unwrap(), 0);
+    }
+
+    #[test]
+    fn bytes_per_sample_of_whole_byte_depths() {
+        for (bits, bytes) in [(8, 1), (16, 2), (24, 3), (32, 4)] {
+            let fmt_chunk = FmtChunk::pcm(1, 8000, bits);
+
+            assert_eq!(fmt_chunk.bytes_per_sample().unwrap(), bytes);
+        }
+    }
+
+    #[test]
+    fn bytes_per_sample_rejects_partial_and_zero_depths() {
+        for bits in [12, 0] {
+            let fmt_chunk = FmtChunk {
+                bits_per_raw_sample: bits,
+                ..FmtChunk::pcm(1, 8000, 16)
+            };
+
+            assert!(matches!(
+                fmt_chunk.bytes_per_sample(),
+                Err(Error::UnsupportedBitDepth(found)) if found == bits
+            ));
+        }
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn open_mmap_matches_buffered_reader() {
+        let path = temp_path("mmap.wav");
+        let bytes = WavBuilder::new()
+            .fmt(2, 44100, 16)
+            .chunk(b"LIST", b"INFO")
+            .data(&[1, 2, 3, 4])
+            .build();
+        std::fs::write(&path, bytes).unwrap();
+
+        let mapped = RiffWaveReader::open_mmap(&path);
+        let buffered = RiffWaveReader::open(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        let mut mapped = mapped.unwrap();
+        let buffered = buffered.unwrap();
+        assert_eq!(mapped.riff_chunk, buffered.riff_chunk);
+        assert_eq!(mapped.fmt_chunk, buffered.fmt_chunk);
+        assert_eq!(mapped.data_chunk, buffered.data_chunk);
+        assert_eq!(mapped.other_chunks, buffered.other_chunks);
+        assert_eq!(mapped.data_offset(), buffered.data_offset());
+        assert_eq!(mapped.data().unwrap().collect::<Vec<_>>(), [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn sentinel_data_size_reads_to_end() {
+        let bytes = WavBuilder::new()
+            .fmt(1, 8000, 8)
+            .chunk_sized(b"data", 0xffff_ffff, &[1, 2, 3])
+            .build();
+        let mut reader = RiffWaveReader::from_bytes(bytes).unwrap();
+
+        assert!(reader.data_chunk.is_unsized);
+        assert_eq!(reader.data_chunk.data_size, 3);
+        assert_eq!(reader.num_frames(), 3);
+        assert_eq!(reader.data().unwrap().collect::<Vec<_>>(), [1, 2, 3]);
+    }
+
+    #[test]
+    fn zero_data_size_at_end_of_file_is_unsized() {
+        let mut reader = WavBuilder::new()
+            .fmt(1, 8000, 8)
+            .chunk_sized(b"data", 0, &[1, 2])
+            .reader();
+
+        assert!(reader.data_chunk.is_unsized);
+        assert_eq!(reader.data().unwrap().collect::<Vec<_>>(), [1, 2]);
+    }
+
+    #[test]
+    fn zero_data_size_followed_by_a_chunk_is_empty() {
+        let mut reader = WavBuilder::new()
+            .fmt(1, 8000, 8)
+            .data(&[])
+            .chunk(b"LIST", b"INFO")
+            .reader();
+
+        assert!(!reader.data_chunk.is_unsized);
+        assert_eq!(reader.data_chunk.data_size, 0);
+        assert_eq!(reader.data().unwrap().count(), 0);
+        assert_eq!(reader.chunk_layout().last().unwrap().id.to_string(), "LIST");
+    }
+
+    #[test]
+    fn unsized_data_stops_at_riff_end() {
+        let mut bytes = WavBuilder::new()
+            .fmt(1, 8000, 8)
+            .chunk_sized(b"data", 0xffff_ffff, &[1, 2])
+            .build();
+        bytes.extend(WavBuilder::new().fmt(1, 8000, 8).data(&[3, 4]).build());
+
+        let mut reader = RiffWaveReader::from_bytes(bytes).unwrap();
+
+        assert!(reader.data_chunk.is_unsized);
+        assert_eq!(reader.data().unwrap().collect::<Vec<_>>(), [1, 2]);
+    }
+
+    #[test]
+    fn unsized_data_with_unsized_riff_reads_to_stream_end() {
+        let mut bytes = WavBuilder::new()
+            .fmt(1, 8000, 8)
+            .chunk_sized(b"data", 0, &[1, 2, 3])
+            .build();
+        bytes[4..8].copy_from_slice(&0xffff_ffffu32.to_le_bytes());
+
+        let mut reader = RiffWaveReader::from_bytes(bytes).unwrap();
+
+        assert!(reader.data_chunk.is_unsized);
+        assert_eq!(reader.data().unwrap().collect::<Vec<_>>(), [1, 2, 3]);
+    }
+
+    #[test]
+    fn indexed_samples_share_the_frame_index_of_their_frame() {
+        let mut reader = WavBuilder::new()
+            .fmt(2, 8000, 16)
+            .data(&pcm16(Endianness::Little, &[1, -1, 2, -2]))
+            .reader();
+
+        assert_eq!(
+            reader.indexed_samples_i16().unwrap().collect::<Vec<_>>(),
+            [(0, 1), (0, -1), (1, 2), (1, -2)]
+        );
+    }
+
+    #[test]
+    fn ixml_chunk_is_read_as_a_string() {
+        let xml = "<BWFXML><PROJECT>Take 1</PROJECT></BWFXML>";
+        let mut body = xml.as_bytes().to_vec();
+        body.extend_from_slice(b"\0\0\n");
+
+        let reader = WavBuilder::new()
+            .fmt(1, 8000, 8)
+            .chunk(b"iXML", &body)
+            .data(&[0])
+            .reader();
+
+        assert_eq!(reader.ixml.as_deref(), Some(xml));
+    }
+
+    #[test]
+    fn format_tags_round_trip() {
+        let formats = [
+            (Format::UncompressedPCM, 1),
+            (Format::IeeeFloatingPoint, 3),
+            (Format::G711ALaw, 6),
+            (Format::G711ULaw, 7),
+            (Format::ExtendedWave, 65534),
+            (Format::Other(0x55), 0x55),
+        ];
+
+        for (format, tag) in formats {
+            assert_eq!(format.as_tag(), tag);
+            assert_eq!(Format::from(format.as_tag()), format);
+        }
+    }
+
+    #[test]
+    fn chunk_claiming_two_gigabytes_is_too_large() {
+        let bytes = WavBuilder::new()
+            .fmt(1, 8000, 8)
+            .chunk_sized(b"fact", 0x8000_0000, &[0; 4])
+            .build();
+
+        assert!(matches!(
+            RiffWaveReader::from_bytes(bytes),
+            Err(Error::ChunkTooLarge {
+                size: 0x8000_0000,
+                limit: DEFAULT_MAX_CHUNK_SIZE
+            })
+        ));
+    }
+
+    #[test]
+    fn chunk_size_limit_is_configurable() {
+        let bytes = WavBuilder::new()
+            .fmt(1, 8000, 8)
+            .chunk(b"LIST", &[0; 64])
+            .data(&[0])
+            .build();
+        let options = ParseOptions {
+            max_chunk_size: 32,
+            ..ParseOptions::default()
+        };
+
+        assert!(matches!(
+            RiffWaveReader::with_options(Cursor::new(bytes), options),
+            Err(Error::ChunkTooLarge {
+                size: 64,
+                limit: 32
+            })
+        ));
+    }
+
+    #[test]
+    fn read_all_channels_separates_stereo_samples() {
+        let mut reader = WavBuilder::new()
+            .fmt(2, 8000, 16)
+            .data(&pcm16(Endianness::Little, &[1, -1, 2, -2, 3, -3]))
+            .reader();
+
+        let channels = reader.read_all_channels_i16().unwrap();
+
+        assert_eq!(channels.len(), 2);
+        assert_eq!(channels[0], [1, 2, 3]);
+        assert_eq!(channels[1], [-1, -2, -3]);
+    }
+
+    #[test]
+    fn read_all_channels_rejects_other_depths_and_partial_frames() {
+        let mut reader = WavBuilder::new().fmt(2, 8000, 8).data(&[0; 4]).reader();
+        assert!(matches!(
+            reader.read_all_channels_i16(),
+            Err(Error::UnsupportedBitDepth(8))
+        ));
+
+        let mut reader = WavBuilder::new()
+            .fmt(2, 8000, 16)
+            .data(&pcm16(Endianness::Little, &[1, -1, 2]))
+            .reader();
+        assert!(matches!(
+            reader.read_all_channels_i16(),
+            Err(Error::IncompleteFrame)
+        ));
+    }
+
+    #[test]
+    fn parsed_fmt_chunk_equals_expected_literal() {
+        let reader = WavBuilder::new().fmt(2, 44100, 16).data(&[0; 4]).reader();
+
+        let expected = FmtChunk {
+            id: FourCC::Fmt,
+            data_size: 16,
+            format: Format::UncompressedPCM,
+            num_channels: 2,
+            sample_rate: 44100,
+            byte_rate: 176_400,
+            block_align: 4,
+            bits_per_raw_sample: 16,
+            extra_info_size: 0,
+            extended_info: None,
+        };
+
+        assert_eq!(reader.fmt_chunk, expected);
+        assert_eq!(
+            reader.data_chunk,
+            DataChunk {
+                id: FourCC::Data,
+                data_size: 4,
+                pad_byte: 0,
+                is_unsized: false,
+            }
+        );
+    }
+
+    #[test]
+    fn known_format_codes_display_their_name() {
+        assert_eq!(Format::Other(0x55).to_string(), "MPEG Layer 3");
+        assert_eq!(Format::Other(0x11).to_string(), "IMA ADPCM");
+        assert_eq!(Format::UncompressedPCM.to_string(), "PCM");
+    }
+
+    #[test]
+    fn unknown_format_codes_display_their_value() {
+        assert_eq!(Format::Other(0x1234).to_string(), "Unknown (0x1234)");
+    }
+
+    #[test]
+    fn next_file_parses_concatenated_files() {
+        let mut bytes = WavBuilder::new().fmt(1, 8000, 8).data(&[1, 2, 3]).build();
+        bytes.extend(WavBuilder::new().fmt(2, 44100, 16).data(&[0; 4]).build());
+
+        let first = RiffWaveReader::from_bytes(bytes).unwrap();
+        assert_eq!(first.fmt_chunk.sample_rate, 8000);
+
+        let mut second = first.next_file().unwrap().unwrap();
+        assert_eq!(second.fmt_chunk.sample_rate, 44100);
+        assert_eq!(second.data_offset(), 48 + 44);
+        assert_eq!(second.data().unwrap().collect::<Vec<_>>(), [0; 4]);
+
+        assert!(second.next_file().unwrap().is_none());
+    }
+
+    #[test]
+    fn files_of_a_concatenated_stream_match_their_riff_size() {
+        let mut bytes = WavBuilder::new().fmt(1, 8000, 8).data(&[1, 2, 3]).build();
+        bytes.extend(WavBuilder::new().fmt(1, 8000, 8).data(&[4]).build());
+
+        let options = ParseOptions {
+            strict: true,
+            ..ParseOptions::default()
+        };
+        let mut first = RiffWaveReader::with_options(Cursor::new(bytes), options).unwrap();
+        first.verify_file_size().unwrap();
+
+        let mut second = first.next_file().unwrap().unwrap();
+        second.verify_file_size().unwrap();
+    }
+
+    #[test]
+    fn file_followed_by_junk_is_a_size_mismatch() {
+        let mut bytes = WavBuilder::new().fmt(1, 8000, 8).data(&[0; 2]).build();
+        bytes.extend_from_slice(&[0; 16]);
+
+        let mut reader = RiffWaveReader::from_bytes(bytes).unwrap();
+
+        assert!(matches!(
+            reader.verify_file_size(),
+            Err(Error::FileSizeMismatch {
+                declared: 46,
+                actual: 62
+            })
+        ));
+    }
+
+    #[test]
+    fn extended_info_overrunning_fmt_chunk_is_invalid() {
+        let mut body = fixture::fmt_body(Endianness::Little, 1, 1, 8000, 16);
+        body.extend_from_slice(&22u16.to_le_bytes());
+        let bytes = WavBuilder::new()
+            .chunk(b"fmt ", &body)
+            .chunk(b"LIST", &[0; 22])
+            .data(&[0; 2])
+            .build();
+
+        assert!(matches!(
+            RiffWaveReader::from_bytes(bytes),
+            Err(Error::Parse {
+                offset: 12,
+                kind: ParseKind::InvalidExtendedInfo
+            })
+        ));
+    }
+
+    #[test]
+    fn headers_only_parses_file_without_data() {
+        let bytes = WavBuilder::new()
+            .fmt(2, 48000, 24)
+            .chunk(b"fact", &100u32.to_le_bytes())
+            .build();
+
+        let headers = RiffWaveReader::new_headers_only(Cursor::new(bytes)).unwrap();
+
+        assert_eq!(headers.fmt_chunk, FmtChunk::pcm(2, 48000, 24));
+        assert_eq!(headers.fact_chunk.unwrap().sample_length, 100);
+        assert!(headers.data_chunk.is_none());
+    }
+
+    #[test]
+    fn headers_only_stops_at_the_data_chunk() {
+        let bytes = WavBuilder::new().fmt(1, 8000, 8).data(&[1, 2]).build();
+        let mut cursor = Cursor::new(bytes);
+
+        let headers = RiffWaveReader::new_headers_only(&mut cursor).unwrap();
+
+        assert_eq!(headers.data_chunk.unwrap().data_size, 2);
+        assert_eq!(cursor.position(), 44);
+    }
+
+    #[test]
+    fn sample_iterators_stop_before_trailing_chunks() {
+        let mut reader = WavBuilder::new()
+            .fmt(1, 8000, 16)
+            .data(&pcm16(Endianness::Little, &[1, 2, 3]))
+            .chunk(b"LIST", b"INFOISFT\x02\0\0\0x\0")
+            .reader();
+
+        let samples = reader.samples_i16().unwrap().collect::<Vec<_>>();
+
+        assert_eq!(samples.len() as u64, reader.data_chunk.data_size / 2);
+        assert_eq!(samples, [1, 2, 3]);
+    }
+
+    #[test]
+    fn downmix_averages_and_rounds_each_frame() {
+        let mut reader = WavBuilder::new()
+            .fmt(2, 8000, 16)
+            .data(&pcm16(
+                Endianness::Little,
+                &[100, 201, i16::MAX, i16::MAX, i16::MIN, i16::MIN, -3, 0],
+            ))
+            .reader();
+
+        assert_eq!(
+            reader.downmix_mono_i16().unwrap(),
+            [151, i16::MAX, i16::MIN, -2]
+        );
+    }
+
+    #[test]
+    fn downmix_handles_any_channel_count() {
+        let mut reader = WavBuilder::new()
+            .fmt(3, 8000, 16)
+            .data(&pcm16(Endianness::Little, &[3, 6, 9, -1, -1, -1]))
+            .reader();
+
+        assert_eq!(reader.downmix_mono_i16().unwrap(), [6, -1]);
+    }
+
+    #[test]
+    fn with_length_bounds_unsized_data() {
+        let bytes = WavBuilder::new()
+            .fmt(1, 8000, 8)
+            .chunk_sized(b"data", 0xffff_ffff, &[1, 2, 3, 4])
+            .build();
+
+        let mut reader = RiffWaveReader::with_length(Cursor::new(bytes), 46).unwrap();
+
+        assert!(reader.data_chunk.is_unsized);
+        assert_eq!(reader.data_chunk.data_size, 2);
+        assert_eq!(reader.data().unwrap().collect::<Vec<_>>(), [1, 2]);
+    }
+
+    #[test]
+    fn frame_math_of_maximal_data_size_does_not_overflow() {
+        let data_chunk = DataChunk {
+            data_size: u64::MAX,
+            ..DataChunk::empty()
+        };
+
+        assert_eq!(data_chunk.num_frames(4), u64::MAX / 4);
+        assert_eq!(data_chunk.num_samples(4, 2), u64::MAX / 4 * 2);
+        assert_eq!(data_chunk.num_samples(1, 8), u64::MAX);
+        assert_eq!(data_chunk.num_frames(0), 0);
+    }
+
+    #[test]
+    fn duration_and_seek_of_maximal_data_size_do_not_panic() {
+        let mut reader = WavBuilder::new().fmt(2, 44100, 16).data(&[0; 4]).reader();
+        reader.data_chunk.data_size = u64::MAX;
+
+        let num_frames = u64::MAX / 4;
+        assert_eq!(reader.num_frames(), num_frames);
+        assert_eq!(reader.duration().as_secs(), num_frames / 44100);
+
+        assert!(matches!(
+            reader.seek_to_frame(num_frames),
+            Err(Error::SeekOutOfRange)
+        ));
+        assert!(matches!(
+            reader.seek_to_frame(u64::MAX),
+            Err(Error::SeekOutOfRange)
+        ));
+    }
+
+    #[test]
+    fn pcm_fmt_chunk_derives_its_sizes() {
+        let fmt_chunk = FmtChunk::pcm(2, 44100, 16);
+
+        assert_eq!(fmt_chunk.id, FourCC::Fmt);
+        assert_eq!(fmt_chunk.format, Format::UncompressedPCM);
+        assert_eq!(fmt_chunk.data_size, 16);
+        assert_eq!(fmt_chunk.block_align, 4);
+        assert_eq!(fmt_chunk.byte_rate, 176_400);
+        assert!(fmt_chunk.validate().is_ok());
+    }
+
+    #[test]
+    fn pcm_fmt_chunk_rounds_partial_bytes_up() {
+        let fmt_chunk = FmtChunk::pcm(1, 8000, 12);
+
+        assert_eq!(fmt_chunk.block_align, 2);
+        assert_eq!(fmt_chunk.byte_rate, 16000);
+    }
+
+    #[test]
+    fn clipped_16_bit_samples_are_counted() {
+        let mut reader = WavBuilder::new()
+            .fmt(1, 8000, 16)
+            .data(&pcm16(
+                Endianness::Little,
+                &[i16::MAX, 0, i16::MIN, 32766, i16::MAX, -32767],
+            ))
+            .reader();
+
+        assert_eq!(reader.count_clipped_samples().unwrap(), 3);
+    }
+
+    #[test]
+    fn clipped_8_bit_samples_are_counted() {
+        let mut reader = WavBuilder::new()
+            .fmt(1, 8000, 8)
+            .data(&[0, 128, 255, 1, 254])
+            .reader();
+
+        assert_eq!(reader.count_clipped_samples().unwrap(), 2);
+    }
+
+    #[test]
+    fn garbled_fmt_id_is_recovered_without_a_fmt_chunk() {
+        let bytes = WavBuilder::new()
+            .chunk(
+                b"fm\0t",
+                &fixture::fmt_body(Endianness::Little, 1, 1, 8000, 16),
+            )
+            .data(&pcm16(Endianness::Little, &[1, 2]))
+            .build();
+
+        assert!(matches!(
+            RiffWaveReader::from_bytes(bytes.clone()),
+            Err(Error::Parse {
+                offset: 36,
+                kind: ParseKind::MissingFmtChunk
+            })
+        ));
+
+        let options = ParseOptions {
+            recover_missing_fmt: true,
+            ..ParseOptions::default()
         };
+        let mut reader = RiffWaveReader::with_options(Cursor::new(bytes), options).unwrap();
 
-        let other_chunks = {
-            let chunk_ids = self
-                .other_chunks
+        assert!(!reader.has_fmt_chunk());
+        assert!(reader.samples_i16().is_err());
+
+        reader.set_fmt_chunk(FmtChunk::pcm(1, 8000, 16));
+        assert!(reader.has_fmt_chunk());
+        assert_eq!(reader.samples_i16().unwrap().collect::<Vec<_>>(), [1, 2]);
+    }
+
+    #[test]
+    fn stereo_16_bit_data_chunk_counts_samples_and_frames() {
+        let data_chunk = DataChunk {
+            data_size: 1000,
+            ..DataChunk::empty()
+        };
+
+        assert_eq!(data_chunk.num_samples(4, 2), 500);
+        assert_eq!(data_chunk.num_frames(4), 250);
+    }
+
+    #[test]
+    fn partial_frames_are_not_counted_as_samples() {
+        let data_chunk = DataChunk {
+            data_size: 1002,
+            ..DataChunk::empty()
+        };
+
+        assert_eq!(data_chunk.num_frames(4), 250);
+        assert_eq!(data_chunk.num_samples(4, 2), 500);
+    }
+
+    #[test]
+    fn fourcc_try_from_slice_rejects_wrong_lengths() {
+        assert!(FourCC::try_from_slice(b"fmt").is_err());
+        assert!(FourCC::try_from_slice(b"fmt  ").is_err());
+        assert_eq!(FourCC::try_from_slice(b"fmt ").unwrap(), FourCC::Fmt);
+        assert_eq!(
+            FourCC::try_from_slice(b"LIST").unwrap(),
+            FourCC::Other(String::from("LIST"))
+        );
+    }
+
+    #[test]
+    fn position_after_seek_to_frame_is_relative_to_data_offset() {
+        let mut reader = WavBuilder::new()
+            .fmt(2, 8000, 16)
+            .chunk(b"LIST", b"INFO")
+            .data(&[0; 80])
+            .reader();
+
+        assert_eq!(reader.position().unwrap(), reader.data_offset());
+
+        reader.seek_to_frame(10).unwrap();
+
+        assert_eq!(reader.position().unwrap(), reader.data_offset() + 10 * 4);
+    }
+
+    #[test]
+    fn fmt_chunk_with_zero_cb_size_is_followed_by_fact() {
+        let mut body = fixture::fmt_body(Endianness::Little, 3, 1, 8000, 32);
+        body.extend_from_slice(&0u16.to_le_bytes());
+
+        let reader = WavBuilder::new()
+            .chunk(b"fmt ", &body)
+            .chunk(b"fact", &2u32.to_le_bytes())
+            .data(&[0; 8])
+            .reader();
+
+        assert_eq!(reader.fmt_chunk.data_size, 18);
+        assert_eq!(reader.fmt_chunk.extra_info_size, 0);
+        assert!(reader.fmt_chunk.extended_info.is_none());
+        assert_eq!(reader.fact_chunk.unwrap().sample_length, 2);
+        assert_eq!(reader.data_chunk.data_size, 8);
+    }
+
+    #[test]
+    fn id3_chunk_bytes_are_kept_verbatim() {
+        let tag = b"ID3\x04\x00\x00\x00\x00\x00\x01\x00";
+
+        for id in [b"id3 ", b"ID3 "] {
+            let reader = WavBuilder::new()
+                .fmt(1, 8000, 8)
+                .chunk(id, tag)
+                .data(&[0; 2])
+                .reader();
+
+            assert_eq!(reader.id3.as_deref(), Some(&tag[..]));
+        }
+    }
+
+    #[test]
+    fn into_parts_returns_the_reader_and_its_chunks() {
+        let reader = WavBuilder::new()
+            .fmt(2, 44100, 16)
+            .chunk(b"LIST", b"INFO")
+            .data(&[1, 2, 3, 4])
+            .reader();
+        let fmt = reader.fmt_chunk.clone();
+        let data_offset = reader.data_offset();
+
+        let (mut inner, riff, fmt_chunk, fact, data, other_chunks) = reader.into_parts();
+
+        assert_eq!(fmt_chunk, fmt);
+        assert_eq!(riff.file_type, FourCC::Wave);
+        assert!(fact.is_none());
+        assert_eq!(data.data_size, 4);
+        assert_eq!(other_chunks.len(), 1);
+        assert_eq!(inner.stream_position().unwrap(), data_offset);
+    }
+
+    #[test]
+    fn cloned_reader_reads_independently() {
+        let mut reader = WavBuilder::new()
+            .fmt(1, 8000, 8)
+            .data(&[1, 2, 3, 4])
+            .reader();
+        reader.seek_to_frame(1).unwrap();
+
+        let mut clone = reader.clone();
+
+        assert_eq!(reader.data().unwrap().collect::<Vec<_>>(), [2, 3, 4]);
+        assert_eq!(clone.data().unwrap().collect::<Vec<_>>(), [2, 3, 4]);
+        assert_eq!(clone.fmt_chunk, reader.fmt_chunk);
+    }
+
+    #[test]
+    fn audio_spec_resolves_extensible_24_bit_files() {
+        let reader = WavBuilder::new()
+            .fmt_extensible(1, 2, 48000, 24, 0x3)
+            .data(&[0; 12])
+            .reader();
+
+        assert_eq!(reader.fmt_chunk.format, Format::ExtendedWave);
+        assert_eq!(
+            reader.audio_spec(),
+            AudioSpec {
+                format: Format::UncompressedPCM,
+                bits_per_sample: 24,
+                sample_rate: 48000,
+                channels: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn find_silence_reports_quiet_frame_ranges() {
+        let samples = [1000, -1000, 3, -2, 0, 1, -4, 0, 2000, 0, 1, 0, -1, 0, 0, 0];
+        let mut reader = WavBuilder::new()
+            .fmt(2, 8000, 16)
+            .data(&pcm16(Endianness::Little, &samples))
+            .reader();
+
+        let ranges = reader.find_silence(10, 2).unwrap();
+
+        assert_eq!(ranges, [(1, 4), (5, 8)]);
+        assert!(reader.find_silence(10, 1).unwrap().is_empty());
+    }
+
+    #[test]
+    fn rewind_data_allows_reading_samples_again() {
+        let mut reader = WavBuilder::new()
+            .fmt(1, 8000, 16)
+            .data(&pcm16(Endianness::Little, &[1, -2, 300]))
+            .reader();
+
+        let first = reader.samples_i16().unwrap().collect::<Vec<_>>();
+        assert_eq!(reader.data().unwrap().count(), 0);
+
+        reader.rewind_data().unwrap();
+        let second = reader.samples_i16().unwrap().collect::<Vec<_>>();
+
+        assert_eq!(first, [1, -2, 300]);
+        assert_eq!(second, first);
+    }
+
+    #[cfg(feature = "hound-interop")]
+    #[test]
+    fn to_hound_spec_maps_16_bit_stereo() {
+        let reader = WavBuilder::new().fmt(2, 44100, 16).data(&[0; 8]).reader();
+
+        assert_eq!(
+            reader.to_hound_spec(),
+            hound::WavSpec {
+                channels: 2,
+                sample_rate: 44100,
+                bits_per_sample: 16,
+                sample_format: hound::SampleFormat::Int,
+            }
+        );
+    }
+
+    #[test]
+    fn truncated_data_is_reported() {
+        let bytes = WavBuilder::new()
+            .fmt(1, 8000, 8)
+            .chunk_sized(b"data", 8, &[1, 2, 3, 4])
+            .build();
+
+        let mut reader = RiffWaveReader::from_bytes(bytes.clone()).unwrap();
+        assert!(reader.is_truncated());
+        assert_eq!(reader.available_data_size(), 4);
+        assert_eq!(reader.data().unwrap().collect::<Vec<_>>(), [1, 2, 3, 4]);
+
+        let (_, warnings) = RiffWaveReader::new_collect_errors(Cursor::new(bytes.clone())).unwrap();
+        assert!(warnings.iter().any(|warning| matches!(
+            warning.0,
+            Error::TruncatedData {
+                declared: 8,
+                available: 4
+            }
+        )));
+
+        let strict = ParseOptions {
+            strict: true,
+            ..ParseOptions::default()
+        };
+        assert!(matches!(
+            RiffWaveReader::with_options(Cursor::new(bytes), strict),
+            Err(Error::TruncatedData {
+                declared: 8,
+                available: 4
+            })
+        ));
+    }
+
+    #[test]
+    fn crc32_matches_known_values() {
+        let mut reader = WavBuilder::new()
+            .fmt(1, 8000, 8)
+            .chunk(b"note", b"123456789")
+            .data(b"123456789")
+            .reader();
+
+        assert_eq!(reader.other_chunks[0].crc32(), 0xcbf4_3926);
+        assert_eq!(reader.data_crc32().unwrap(), 0xcbf4_3926);
+        assert_eq!(reader.data().unwrap().count(), 0);
+    }
+
+    #[test]
+    fn hex_dump_headers_shows_each_chunk() {
+        let mut reader = WavBuilder::new()
+            .fmt(1, 8000, 16)
+            .data(&pcm16(Endianness::Little, &[1, 2]))
+            .reader();
+
+        let dump = reader.hex_dump_headers().unwrap();
+        let lines = dump.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("fmt  offset         12 size         16"));
+        assert!(lines[0]
+            .ends_with("id 66 6d 74 20 data 01 00 01 00 40 1f 00 00 80 3e 00 00 02 00 10 00"));
+        assert!(lines[1].contains("offset         36 size          4 id 64 61 74 61"));
+        assert_eq!(reader.samples_i16().unwrap().collect::<Vec<_>>(), [1, 2]);
+    }
+
+    #[test]
+    fn looped_samples_repeat_the_loop_region() {
+        let smpl = [0, 0, 125_000, 60, 0, 0, 0, 1, 0, 0, 0, 1, 2, 0, 0]
+            .iter()
+            .flat_map(|n: &u32| n.to_le_bytes())
+            .collect::<Vec<_>>();
+        let mut reader = WavBuilder::new()
+            .fmt(1, 8000, 16)
+            .chunk(b"smpl", &smpl)
+            .data(&pcm16(Endianness::Little, &[10, 20, 30, 40, 50]))
+            .reader();
+
+        let samples = reader.looped_samples_i16(0, 3).unwrap().collect::<Vec<_>>();
+
+        assert_eq!(samples.len(), 1 + 3 * 2 + 2);
+        assert_eq!(samples, [10, 20, 30, 20, 30, 20, 30, 40, 50]);
+        assert!(matches!(
+            reader.looped_samples_i16(1, 3),
+            Err(Error::InvalidLoop(1))
+        ));
+    }
+
+    #[test]
+    fn same_values_decode_from_either_byte_order() {
+        let mut little = Cursor::new(vec![0x34, 0x12, 0x78, 0x56, 0x34, 0x12]);
+        let mut big = Cursor::new(vec![0x12, 0x34, 0x12, 0x34, 0x56, 0x78]);
+
+        assert_eq!(little.read_u16(Endianness::Little).unwrap(), 0x1234);
+        assert_eq!(big.read_u16(Endianness::Big).unwrap(), 0x1234);
+        assert_eq!(little.read_u32(Endianness::Little).unwrap(), 0x1234_5678);
+        assert_eq!(big.read_u32(Endianness::Big).unwrap(), 0x1234_5678);
+
+        assert_eq!(Endianness::Little.read_i24(&[0xfe, 0xff, 0xff]), -2);
+        assert_eq!(Endianness::Big.read_i24(&[0xff, 0xff, 0xfe]), -2);
+        assert_eq!(Endianness::Little.read_f32(&1.5f32.to_le_bytes()), 1.5);
+        assert_eq!(Endianness::Big.read_f32(&1.5f32.to_be_bytes()), 1.5);
+
+        let samples = [1, -2, 300, i16::MIN];
+        let mut riff = WavBuilder::new()
+            .fmt(1, 8000, 16)
+            .data(&pcm16(Endianness::Little, &samples))
+            .reader();
+        let mut rifx = WavBuilder::rifx()
+            .fmt(1, 8000, 16)
+            .data(&pcm16(Endianness::Big, &samples))
+            .reader();
+
+        assert_eq!(riff.samples_i16().unwrap().collect::<Vec<_>>(), samples);
+        assert_eq!(rifx.samples_i16().unwrap().collect::<Vec<_>>(), samples);
+    }
+
+    #[test]
+    fn present_chunks_lists_recognized_chunks() {
+        let reader = WavBuilder::new()
+            .fmt(1, 8000, 8)
+            .chunk(b"fact", &[2, 0, 0, 0])
+            .chunk(b"junk", &[0; 4])
+            .data(&[1, 2])
+            .reader();
+
+        assert_eq!(
+            reader.present_chunks(),
+            [FourCC::Fmt, FourCC::Fact, FourCC::Data]
+        );
+
+        let reader = WavBuilder::new()
+            .fmt(1, 8000, 8)
+            .chunk(b"LIST", b"INFO")
+            .data(&[1, 2])
+            .reader();
+
+        assert_eq!(
+            reader.present_chunks(),
+            [
+                FourCC::Fmt,
+                FourCC::Other(String::from("LIST")),
+                FourCC::Data
+            ]
+        );
+    }
+
+    #[test]
+    fn skipped_padding_chunks_are_only_in_the_layout() {
+        let bytes = WavBuilder::new()
+            .fmt(1, 8000, 8)
+            .chunk(b"JUNK", &[0; 4096])
+            .data(&[1, 2])
+            .build();
+        let options = ParseOptions {
+            skip_padding_chunks: true,
+            ..ParseOptions::default()
+        };
+
+        let mut reader = RiffWaveReader::with_options(Cursor::new(bytes.clone()), options).unwrap();
+
+        assert!(reader.other_chunks.is_empty());
+        assert_eq!(
+            reader
+                .chunk_layout()
                 .iter()
-                .map(|c| {
-                    if let FourCC::Other(id) = &c.id {
-                        id.clone()
-                    } else {
-                        String::from("")
-                    }
-                })
-                .collect::<Vec<_>>();
+                .map(|chunk| (chunk.raw_id, chunk.offset, chunk.size))
+                .collect::<Vec<_>>(),
+            [
+                (*b"fmt ", 12, 16),
+                (*b"JUNK", 36, 4096),
+                (*b"data", 4140, 2)
+            ]
+        );
+        assert_eq!(reader.data().unwrap().collect::<Vec<_>>(), [1, 2]);
 
-            if chunk_ids.is_empty() {
-                String::from("")
-            } else {
-                format!(
-                    "\n--- Other Chunks ---
-Chunk Ids:       {:?}",
-                    chunk_ids
-                )
+        let reader = RiffWaveReader::from_bytes(bytes).unwrap();
+        assert_eq!(reader.other_chunks[0].data.len(), 4096);
+    }
+
+    #[test]
+    fn early_end_of_file_names_the_structure_being_read() {
+        fn eof_context(bytes: Vec<u8>) -> Option<(u64, &'static str)> {
+            match RiffWaveReader::from_bytes(bytes) {
+                Err(Error::Parse {
+                    offset,
+                    kind: ParseKind::UnexpectedEof { context },
+                }) => Some((offset, context)),
+                _ => None,
+            }
+        }
+
+        let bytes = WavBuilder::new().fmt(1, 8000, 16).data(&[0; 4]).build();
+
+        assert_eq!(eof_context(bytes[..6].to_vec()), Some((0, "riff header")));
+        assert_eq!(eof_context(bytes[..30].to_vec()), Some((12, "fmt chunk")));
+        assert_eq!(
+            eof_context(bytes[..40].to_vec()),
+            Some((36, "chunk header"))
+        );
+
+        let bytes = WavBuilder::new()
+            .fmt(1, 8000, 16)
+            .chunk(b"note", &[0; 8])
+            .build();
+        assert_eq!(eof_context(bytes[..50].to_vec()), Some((36, "chunk body")));
+    }
+
+    #[test]
+    fn truncated_metadata_is_a_parse_error() {
+        let bytes = WavBuilder::new()
+            .fmt(1, 8000, 16)
+            .chunk(b"LIST", b"adtllabl\x04\x00")
+            .chunk(b"cue ", &[1, 0])
+            .data(&[0; 4])
+            .build();
+
+        let (reader, warnings) =
+            RiffWaveReader::new_collect_errors(Cursor::new(bytes.clone())).unwrap();
+        assert!(reader.adtl.is_none());
+        assert!(reader.cue_points.is_none());
+        assert!(matches!(
+            warnings[0].0,
+            Error::Parse {
+                offset: 36,
+                kind: ParseKind::UnexpectedEof {
+                    context: "adtl list"
+                }
             }
+        ));
+        assert!(matches!(
+            warnings[1].0,
+            Error::Parse {
+                offset: 54,
+                kind: ParseKind::InvalidCueChunk
+            }
+        ));
+
+        let strict = ParseOptions {
+            strict: true,
+            ..ParseOptions::default()
         };
+        assert!(matches!(
+            RiffWaveReader::with_options(Cursor::new(bytes), strict),
+            Err(Error::Parse {
+                offset: 36,
+                kind: ParseKind::UnexpectedEof { .. }
+            })
+        ));
+    }
 
-        let data = format!(
-            "\n------- Data -------
-Data Length:     {}
-Padding Byte:    {}",
-            self.data_chunk.data_size, self.data_chunk.pad_byte
+    #[test]
+    fn read_all_picks_the_variant_of_the_fmt_chunk() {
+        let mut reader = WavBuilder::new()
+            .fmt(2, 8000, 16)
+            .data(&pcm16(Endianness::Little, &[1, -1, 300, -300]))
+            .reader();
+
+        assert_eq!(
+            reader.read_all().unwrap(),
+            Samples::I16(vec![1, -1, 300, -300])
         );
 
-        write!(
-            f,
-            "------ Header ------
-Size:            {}
-Format:          {:?}
-Channels:        {}
-Sample Rate:     {}
-Byte Rate:       {}
-Block Align:     {}
-Bits per Raw:    {}
-Extra Info:      {}{}{}{}{}",
-            size,
-            format,
-            num_channels,
-            sample_rate,
-            byte_rate,
-            block_align,
-            bits_per_sample,
-            extra_info_size,
-            extended,
-            fact,
-            other_chunks,
-            data
-        )
+        let data = [0.5f32, -1.0]
+            .iter()
+            .flat_map(|sample| sample.to_le_bytes())
+            .collect::<Vec<_>>();
+        let mut reader = WavBuilder::new()
+            .fmt_format(3, 1, 8000, 32)
+            .chunk(b"fact", &[2, 0, 0, 0])
+            .data(&data)
+            .reader();
+
+        assert_eq!(reader.read_all().unwrap(), Samples::F32(vec![0.5, -1.0]));
+    }
+
+    #[test]
+    fn new_collect_errors_records_recovered_problems() {
+        let bytes = WavBuilder::new()
+            .fmt(1, 8000, 16)
+            .chunk_sized(b"note", 3, &[1, 2, 3])
+            .chunk(b"bext", &[0; 8])
+            .data(&pcm16(Endianness::Little, &[1, 2]))
+            .build();
+
+        assert!(RiffWaveReader::from_bytes(bytes.clone()).is_err());
+
+        let (mut reader, warnings) =
+            RiffWaveReader::new_collect_errors(Cursor::new(bytes)).unwrap();
+
+        let warnings = warnings
+            .iter()
+            .map(|warning| match &warning.0 {
+                Error::Parse { offset, kind } => (*offset, kind.to_string()),
+                e => panic!("unexpected warning {}", e),
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            warnings,
+            [
+                (47, ParseKind::MisalignedChunk.to_string()),
+                (63, ParseKind::MisalignedChunk.to_string()),
+                (47, ParseKind::InvalidBextChunk.to_string()),
+            ]
+        );
+        assert!(reader.bext.is_none());
+        assert_eq!(reader.samples_i16().unwrap().collect::<Vec<_>>(), [1, 2]);
     }
 }
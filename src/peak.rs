@@ -0,0 +1,96 @@
+use std::io::Cursor;
+
+use crate::{Endianness, Error, ParseKind, ReadExt};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PeakChunk {
+    pub version: u32,
+    pub timestamp: u32,
+    pub peaks: Vec<PeakPosition>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PeakPosition {
+    pub value: f32,
+    pub position: u32,
+}
+
+pub(crate) fn read_peak_chunk(
+    data: &[u8],
+    offset: u64,
+    endianness: Endianness,
+    num_channels: u16,
+) -> Result<PeakChunk, Error> {
+    if data.len() < 8 || (data.len() as u64 - 8) / 8 < num_channels as u64 {
+        return Err(Error::parse(offset, ParseKind::InvalidPeakChunk));
+    }
+
+    let mut reader = Cursor::new(data);
+
+    let version = reader.read_u32(endianness)?;
+    let timestamp = reader.read_u32(endianness)?;
+
+    let mut peaks = Vec::with_capacity(num_channels as usize);
+
+    for _ in 0..num_channels {
+        let value = f32::from_bits(reader.read_u32(endianness)?);
+        let position = reader.read_u32(endianness)?;
+
+        peaks.push(PeakPosition { value, position });
+    }
+
+    Ok(PeakChunk {
+        version,
+        timestamp,
+        peaks,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn peak_data(peaks: &[(f32, u32)]) -> Vec<u8> {
+        let mut data = [1u32, 1_600_000_000]
+            .iter()
+            .flat_map(|n| n.to_le_bytes())
+            .collect::<Vec<_>>();
+
+        for (value, position) in peaks {
+            data.extend(value.to_le_bytes());
+            data.extend(position.to_le_bytes());
+        }
+
+        data
+    }
+
+    #[test]
+    fn two_channel_peaks_are_read() {
+        let data = peak_data(&[(0.5, 100), (-0.75, 2048)]);
+
+        let peak = read_peak_chunk(&data, 0, Endianness::Little, 2).unwrap();
+
+        assert_eq!(peak.version, 1);
+        assert_eq!(peak.timestamp, 1_600_000_000);
+        assert_eq!(peak.peaks.len(), 2);
+        assert_eq!(peak.peaks[0].value, 0.5);
+        assert_eq!(peak.peaks[0].position, 100);
+        assert_eq!(peak.peaks[1].value, -0.75);
+        assert_eq!(peak.peaks[1].position, 2048);
+    }
+
+    #[test]
+    fn fewer_peaks_than_channels_is_invalid() {
+        let data = peak_data(&[(0.5, 100)]);
+
+        assert!(matches!(
+            read_peak_chunk(&data, 36, Endianness::Little, 2),
+            Err(Error::Parse {
+                offset: 36,
+                kind: ParseKind::InvalidPeakChunk
+            })
+        ));
+    }
+}
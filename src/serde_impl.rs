@@ -0,0 +1,168 @@
+use serde::de::Deserializer;
+use serde::ser::Serializer;
+use serde::{Deserialize, Serialize};
+
+use crate::{Format, FourCC};
+
+impl Serialize for FourCC {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for FourCC {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let id = String::deserialize(deserializer)?;
+
+        if id.len() == 4 {
+            Ok(FourCC::from(id.as_bytes()))
+        } else {
+            Ok(FourCC::Other(id))
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct FormatRepr {
+    #[serde(default)]
+    name: String,
+    tag: u16,
+}
+
+impl Serialize for Format {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let name = match self {
+            Format::Other(_) => "Other".to_string(),
+            _ => format!("{:?}", self),
+        };
+
+        FormatRepr {
+            name,
+            tag: (*self).into(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Format {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = FormatRepr::deserialize(deserializer)?;
+
+        Ok(Format::from(repr.tag))
+    }
+}
+
+/// Serializes byte vectors as a base64 string for human readable formats and
+/// as raw bytes otherwise.
+pub(crate) mod bytes {
+    use base64::engine::general_purpose::STANDARD;
+    use base64::Engine;
+    use serde::de::{Deserializer, Error};
+    use serde::ser::Serializer;
+    use serde::Deserialize;
+
+    pub fn serialize<S: Serializer>(data: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&STANDARD.encode(data))
+        } else {
+            serializer.serialize_bytes(data)
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        if deserializer.is_human_readable() {
+            let encoded = String::deserialize(deserializer)?;
+
+            STANDARD.decode(encoded).map_err(D::Error::custom)
+        } else {
+            deserialize_byte_buf(deserializer)
+        }
+    }
+
+    fn deserialize_byte_buf<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<u8>, D::Error> {
+        struct Visitor;
+
+        impl<'de> serde::de::Visitor<'de> for Visitor {
+            type Value = Vec<u8>;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str("a byte array")
+            }
+
+            fn visit_bytes<E: Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+                Ok(v.to_vec())
+            }
+
+            fn visit_byte_buf<E: Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+                Ok(v)
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<Self::Value, A::Error> {
+                let mut data = vec![];
+                while let Some(byte) = seq.next_element()? {
+                    data.push(byte);
+                }
+                Ok(data)
+            }
+        }
+
+        deserializer.deserialize_byte_buf(Visitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ExtendedInfo, FmtChunk, Format, FourCC};
+
+    #[test]
+    fn fmt_chunk_round_trips() {
+        let fmt_chunk = FmtChunk {
+            format: Format::ExtendedWave,
+            data_size: 40,
+            extra_info_size: 22,
+            extended_info: Some(ExtendedInfo {
+                bits_per_coded_sample: 20,
+                channel_mask: 0x3,
+                sub_format: 0x719b_3800_aa00_0080_0010_0000_0000_0001,
+                remaining_data: vec![1, 2, 3],
+            }),
+            ..FmtChunk::pcm(2, 48000, 24)
+        };
+
+        let json = serde_json::to_string(&fmt_chunk).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value["id"], "fmt ");
+        assert_eq!(value["format"]["name"], "ExtendedWave");
+        assert_eq!(value["format"]["tag"], 65534);
+        assert_eq!(value["extended_info"]["remaining_data"], "AQID");
+        assert_eq!(serde_json::from_str::<FmtChunk>(&json).unwrap(), fmt_chunk);
+    }
+
+    #[test]
+    fn fourcc_serializes_as_its_code() {
+        let json = serde_json::to_string(&FourCC::Other(String::from("LIST"))).unwrap();
+
+        assert_eq!(json, "\"LIST\"");
+        assert_eq!(
+            serde_json::from_str::<FourCC>("\"data\"").unwrap(),
+            FourCC::Data
+        );
+    }
+
+    #[test]
+    fn unknown_format_keeps_its_tag() {
+        let json = serde_json::to_string(&Format::Other(0x55)).unwrap();
+
+        assert_eq!(json, r#"{"name":"Other","tag":85}"#);
+        assert_eq!(
+            serde_json::from_str::<Format>(&json).unwrap(),
+            Format::Other(0x55)
+        );
+    }
+}
@@ -0,0 +1,135 @@
+use std::io::Cursor;
+
+use crate::{Endianness, Error, ParseKind, ReadExt};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SmplChunk {
+    pub manufacturer: u32,
+    pub product: u32,
+    pub sample_period: u32,
+    pub midi_unity_note: u32,
+    pub midi_pitch_fraction: u32,
+    pub smpte_format: u32,
+    pub smpte_offset: u32,
+    pub sample_loops: Vec<SampleLoop>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::bytes"))]
+    pub sampler_data: Vec<u8>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SampleLoop {
+    pub cue_point_id: u32,
+    pub loop_type: u32,
+    pub start: u32,
+    pub end: u32,
+    pub fraction: u32,
+    pub play_count: u32,
+}
+
+pub(crate) fn read_smpl_chunk(
+    data: &[u8],
+    offset: u64,
+    endianness: Endianness,
+) -> Result<SmplChunk, Error> {
+    if data.len() < 36 {
+        return Err(Error::parse(offset, ParseKind::InvalidSmplChunk));
+    }
+
+    let mut reader = Cursor::new(data);
+
+    let manufacturer = reader.read_u32(endianness)?;
+    let product = reader.read_u32(endianness)?;
+    let sample_period = reader.read_u32(endianness)?;
+    let midi_unity_note = reader.read_u32(endianness)?;
+    let midi_pitch_fraction = reader.read_u32(endianness)?;
+    let smpte_format = reader.read_u32(endianness)?;
+    let smpte_offset = reader.read_u32(endianness)?;
+    let num_sample_loops = reader.read_u32(endianness)?;
+    let sampler_data_size = reader.read_u32(endianness)?;
+
+    if (data.len() as u64 - 36) / 24 < num_sample_loops as u64 {
+        return Err(Error::parse(offset, ParseKind::InvalidSmplChunk));
+    }
+
+    let mut sample_loops = Vec::with_capacity(num_sample_loops as usize);
+
+    for _ in 0..num_sample_loops {
+        let cue_point_id = reader.read_u32(endianness)?;
+        let loop_type = reader.read_u32(endianness)?;
+        let start = reader.read_u32(endianness)?;
+        let end = reader.read_u32(endianness)?;
+        let fraction = reader.read_u32(endianness)?;
+        let play_count = reader.read_u32(endianness)?;
+
+        sample_loops.push(SampleLoop {
+            cue_point_id,
+            loop_type,
+            start,
+            end,
+            fraction,
+            play_count,
+        });
+    }
+
+    let remaining = data.len() - reader.position() as usize;
+    let sampler_data = reader.read_bytes(remaining.min(sampler_data_size as usize))?;
+
+    Ok(SmplChunk {
+        manufacturer,
+        product,
+        sample_period,
+        midi_unity_note,
+        midi_pitch_fraction,
+        smpte_format,
+        smpte_offset,
+        sample_loops,
+        sampler_data,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn smpl_data(num_sample_loops: u32, sample_loops: &[[u32; 6]]) -> Vec<u8> {
+        let header = [0, 0, 22675, 60, 0x8000_0000, 0, 0, num_sample_loops, 0];
+
+        header
+            .iter()
+            .chain(sample_loops.iter().flatten())
+            .flat_map(|n| n.to_le_bytes())
+            .collect()
+    }
+
+    #[test]
+    fn sustain_loop_is_read() {
+        let data = smpl_data(1, &[[1, 0, 100, 200, 0, 0]]);
+
+        let smpl = read_smpl_chunk(&data, 0, Endianness::Little).unwrap();
+
+        assert_eq!(smpl.sample_period, 22675);
+        assert_eq!(smpl.midi_unity_note, 60);
+        assert_eq!(smpl.midi_pitch_fraction, 0x8000_0000);
+        assert_eq!(smpl.sample_loops.len(), 1);
+        assert_eq!(smpl.sample_loops[0].cue_point_id, 1);
+        assert_eq!(smpl.sample_loops[0].loop_type, 0);
+        assert_eq!(smpl.sample_loops[0].start, 100);
+        assert_eq!(smpl.sample_loops[0].end, 200);
+        assert!(smpl.sampler_data.is_empty());
+    }
+
+    #[test]
+    fn loop_count_exceeding_chunk_is_invalid() {
+        let data = smpl_data(2, &[[1, 0, 100, 200, 0, 0]]);
+
+        assert!(matches!(
+            read_smpl_chunk(&data, 36, Endianness::Little),
+            Err(Error::Parse {
+                offset: 36,
+                kind: ParseKind::InvalidSmplChunk
+            })
+        ));
+    }
+}
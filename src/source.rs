@@ -0,0 +1,572 @@
+use alloc::vec;
+use core::convert::TryFrom;
+
+use byteorder::{BigEndian, ByteOrder, LittleEndian};
+
+#[cfg(feature = "std")]
+use std::io;
+#[cfg(feature = "std")]
+use std::io::Read;
+
+use crate::{
+    ChunkHeader, DataChunk, Ds64Chunk, Endianness, ExtendedInfo, FactChunk, FmtChunk, Format,
+    FourCC, RiffChunk, DEFAULT_MAX_CHUNK_SIZE,
+};
+
+/// A minimal stand-in for `Read + Seek`, so the headers of a file can be
+/// parsed without `std`.
+pub trait Source {
+    /// Fills `buf` completely, returning [`HeaderError::UnexpectedEof`] if the
+    /// source ends first.
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), HeaderError>;
+
+    /// Advances the position by `len` bytes without reading them.
+    fn skip(&mut self, len: u64) -> Result<(), HeaderError>;
+
+    fn position(&self) -> u64;
+}
+
+/// A [`Source`] reading from an in-memory byte slice.
+#[derive(Debug)]
+pub struct SliceSource<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> SliceSource<'a> {
+    pub fn new(data: &'a [u8]) -> SliceSource<'a> {
+        SliceSource { data, position: 0 }
+    }
+}
+
+impl Source for SliceSource<'_> {
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), HeaderError> {
+        let end = self
+            .position
+            .checked_add(buf.len())
+            .filter(|end| *end <= self.data.len())
+            .ok_or(HeaderError::UnexpectedEof)?;
+
+        buf.copy_from_slice(&self.data[self.position..end]);
+        self.position = end;
+
+        Ok(())
+    }
+
+    fn skip(&mut self, len: u64) -> Result<(), HeaderError> {
+        let len = usize::try_from(len).unwrap_or(usize::MAX);
+        self.position = self.position.saturating_add(len);
+
+        Ok(())
+    }
+
+    fn position(&self) -> u64 {
+        self.position as u64
+    }
+}
+
+/// A [`Source`] reading forward from a stream that can't seek, such as a
+/// socket. Skipped chunk bodies are read and discarded.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct ReadSource<R: Read> {
+    reader: R,
+    position: u64,
+}
+
+#[cfg(feature = "std")]
+impl<R: Read> ReadSource<R> {
+    pub fn new(reader: R) -> ReadSource<R> {
+        ReadSource {
+            reader,
+            position: 0,
+        }
+    }
+
+    /// Returns the underlying reader, positioned after the bytes read so far.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: Read> Source for ReadSource<R> {
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), HeaderError> {
+        self.reader.read_exact(buf)?;
+        self.position += buf.len() as u64;
+
+        Ok(())
+    }
+
+    fn skip(&mut self, len: u64) -> Result<(), HeaderError> {
+        let skipped = io::copy(&mut (&mut self.reader).take(len), &mut io::sink())?;
+        self.position += skipped;
+
+        if skipped < len {
+            return Err(HeaderError::UnexpectedEof);
+        }
+
+        Ok(())
+    }
+
+    fn position(&self) -> u64 {
+        self.position
+    }
+}
+
+/// Error returned when parsing headers from a [`Source`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum HeaderError {
+    UnexpectedEof,
+    NotRiff,
+    NotWave,
+    InvalidDs64Chunk,
+    InvalidFmtChunk,
+    InvalidExtendedInfo,
+    InvalidFactChunk,
+    MissingDataChunk,
+    MissingFmtChunk,
+    /// A header chunk larger than the default size limit.
+    ChunkTooLarge {
+        size: u32,
+    },
+    /// An error other than reaching the end, raised by a [`ReadSource`].
+    #[cfg(feature = "std")]
+    Io(io::ErrorKind),
+}
+
+impl core::fmt::Display for HeaderError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let message = match self {
+            HeaderError::UnexpectedEof => "Unexpected end of source",
+            HeaderError::NotRiff => "Not a riff file",
+            HeaderError::NotWave => "Not a wave format file",
+            HeaderError::InvalidDs64Chunk => "Invalid ds64 chunk",
+            HeaderError::InvalidFmtChunk => "Invalid fmt chunk",
+            HeaderError::InvalidExtendedInfo => {
+                "Invalid Extended Info, less than 22 bytes or larger than the fmt chunk"
+            }
+            HeaderError::InvalidFactChunk => "Invalid fact chunk, less than 4 bytes",
+            HeaderError::MissingDataChunk => "No data chunk found before end of file",
+            HeaderError::MissingFmtChunk => "No fmt chunk found before the data chunk",
+            HeaderError::ChunkTooLarge { size } => {
+                return write!(f, "Chunk of {} bytes exceeds the size limit", size)
+            }
+            #[cfg(feature = "std")]
+            HeaderError::Io(kind) => return write!(f, "IO error: {}", kind),
+        };
+
+        write!(f, "{}", message)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for HeaderError {}
+
+#[cfg(feature = "std")]
+impl From<io::Error> for HeaderError {
+    fn from(e: io::Error) -> Self {
+        match e.kind() {
+            io::ErrorKind::UnexpectedEof => HeaderError::UnexpectedEof,
+            kind => HeaderError::Io(kind),
+        }
+    }
+}
+
+/// The chunks needed to read the data of a file, as parsed by [`read_header`].
+#[derive(Debug)]
+pub struct WaveHeader {
+    pub riff_chunk: RiffChunk,
+    pub fmt_chunk: FmtChunk,
+    pub fact_chunk: Option<FactChunk>,
+    pub ds64_chunk: Option<Ds64Chunk>,
+    pub data_chunk: DataChunk,
+    pub endianness: Endianness,
+    pub data_offset: u64,
+}
+
+/// Parses the headers of a file up to the start of the data chunk, leaving
+/// the source positioned on the first data byte. Chunks other than `fmt `,
+/// `fact` and `ds64` are skipped.
+///
+/// An unsized data chunk keeps its declared size, since a [`Source`] can't
+/// tell how long it is.
+pub fn read_header<S: Source>(source: &mut S) -> Result<WaveHeader, HeaderError> {
+    let riff_chunk = source.read_riff_chunk()?;
+
+    let is_64 = match riff_chunk.id {
+        FourCC::Riff | FourCC::Rifx => false,
+        FourCC::Rf64 | FourCC::Bw64 => true,
+        _ => return Err(HeaderError::NotRiff),
+    };
+
+    if riff_chunk.file_type != FourCC::Wave {
+        return Err(HeaderError::NotWave);
+    }
+
+    let endianness = riff_chunk.endianness();
+
+    let ds64_chunk = if is_64 {
+        match source.read_chunk_header(endianness)? {
+            Some(header) if header.id == FourCC::Ds64 => {
+                if header.data_size > DEFAULT_MAX_CHUNK_SIZE {
+                    return Err(HeaderError::ChunkTooLarge {
+                        size: header.data_size,
+                    });
+                }
+
+                Some(source.read_ds64_chunk(header, endianness)?)
+            }
+            _ => return Err(HeaderError::InvalidDs64Chunk),
+        }
+    } else {
+        None
+    };
+
+    let mut fmt_chunk = None;
+    let mut fact_chunk = None;
+
+    let mut data_chunk = loop {
+        let header = source
+            .read_chunk_header(endianness)?
+            .ok_or(HeaderError::MissingDataChunk)?;
+
+        match header.id {
+            FourCC::Fmt | FourCC::Fact if header.data_size > DEFAULT_MAX_CHUNK_SIZE => {
+                return Err(HeaderError::ChunkTooLarge {
+                    size: header.data_size,
+                })
+            }
+            FourCC::Fmt => fmt_chunk = Some(source.read_fmt_chunk(header, endianness)?),
+            FourCC::Fact => fact_chunk = Some(source.read_fact_chunk(header, endianness)?),
+            FourCC::Data => {
+                if fmt_chunk.is_none() {
+                    return Err(HeaderError::MissingFmtChunk);
+                }
+
+                break DataChunk::from_header(&header);
+            }
+            _ => source.skip(header.data_size as u64 + (header.data_size % 2) as u64)?,
+        }
+    };
+
+    if let Some(ds64_chunk) = &ds64_chunk {
+        if data_chunk.data_size == 0xffff_ffff {
+            data_chunk.data_size = ds64_chunk.data_chunk_size;
+            data_chunk.pad_byte = (ds64_chunk.data_chunk_size % 2) as u8;
+            data_chunk.is_unsized = false;
+        }
+    }
+
+    Ok(WaveHeader {
+        riff_chunk,
+        fmt_chunk: fmt_chunk.unwrap(),
+        fact_chunk,
+        ds64_chunk,
+        data_chunk,
+        endianness,
+        data_offset: source.position(),
+    })
+}
+
+/// The chunk parsers shared by [`read_header`] and the `std` readers, which
+/// adapt their streams to [`Source`].
+pub(crate) trait SourceExt: Source {
+    fn read_riff_chunk(&mut self) -> Result<RiffChunk, HeaderError> {
+        let id = self.read_fourcc()?;
+        let endianness = if id == FourCC::Rifx {
+            Endianness::Big
+        } else {
+            Endianness::Little
+        };
+
+        let file_size = self.read_u32(endianness)?;
+        let file_type = self.read_fourcc()?;
+
+        Ok(RiffChunk {
+            id,
+            file_size,
+            file_type,
+        })
+    }
+
+    fn read_chunk_header(
+        &mut self,
+        endianness: Endianness,
+    ) -> Result<Option<ChunkHeader>, HeaderError> {
+        let offset = self.position();
+
+        let mut raw_id = [0; 4];
+        match self.read_exact(&mut raw_id) {
+            Ok(_) => {}
+            Err(HeaderError::UnexpectedEof) => return Ok(None),
+            Err(e) => return Err(e),
+        }
+
+        let data_size = self.read_u32(endianness)?;
+
+        Ok(Some(ChunkHeader {
+            id: FourCC::from(raw_id),
+            raw_id,
+            data_size,
+            offset,
+        }))
+    }
+
+    fn read_ds64_chunk(
+        &mut self,
+        header: ChunkHeader,
+        endianness: Endianness,
+    ) -> Result<Ds64Chunk, HeaderError> {
+        let ChunkHeader { id, data_size, .. } = header;
+
+        if data_size < 24 {
+            return Err(HeaderError::InvalidDs64Chunk);
+        }
+
+        let riff_size = self.read_u64(endianness)?;
+        let data_chunk_size = self.read_u64(endianness)?;
+        let sample_count = self.read_u64(endianness)?;
+
+        let mut remaining_data = vec![0; (data_size - 24) as usize];
+        self.read_exact(&mut remaining_data)?;
+        self.skip((data_size % 2) as u64)?;
+
+        Ok(Ds64Chunk {
+            id,
+            data_size,
+            riff_size,
+            data_chunk_size,
+            sample_count,
+            remaining_data,
+        })
+    }
+
+    fn read_fmt_chunk(
+        &mut self,
+        header: ChunkHeader,
+        endianness: Endianness,
+    ) -> Result<FmtChunk, HeaderError> {
+        let ChunkHeader { id, data_size, .. } = header;
+
+        if data_size < 16 {
+            return Err(HeaderError::InvalidFmtChunk);
+        }
+
+        let format = Format::from(self.read_u16(endianness)?);
+        let num_channels = self.read_u16(endianness)?;
+        let sample_rate = self.read_u32(endianness)?;
+        let byte_rate = self.read_u32(endianness)?;
+        let block_align = self.read_u16(endianness)?;
+        let bits_per_raw_sample = self.read_u16(endianness)?;
+
+        let mut read = 16;
+
+        let (extra_info_size, extended_info) = if data_size >= 18 {
+            let extra_info_size = self.read_u16(endianness)?;
+            read += 2;
+
+            if extra_info_size == 0 {
+                (0, None)
+            } else if extra_info_size < 22 || data_size - read < extra_info_size as u32 {
+                return Err(HeaderError::InvalidExtendedInfo);
+            } else {
+                let bits_per_coded_sample = self.read_u16(endianness)?;
+                let channel_mask = self.read_u32(endianness)?;
+                let sub_format = self.read_guid(endianness)?;
+
+                let mut remaining_data = vec![0; (extra_info_size - 22) as usize];
+                self.read_exact(&mut remaining_data)?;
+                read += extra_info_size as u32;
+
+                (
+                    extra_info_size,
+                    Some(ExtendedInfo {
+                        bits_per_coded_sample,
+                        channel_mask,
+                        sub_format,
+                        remaining_data,
+                    }),
+                )
+            }
+        } else {
+            (0, None)
+        };
+
+        self.skip((data_size - read) as u64 + (data_size % 2) as u64)?;
+
+        Ok(FmtChunk {
+            id,
+            data_size,
+            format,
+            num_channels,
+            sample_rate,
+            byte_rate,
+            block_align,
+            bits_per_raw_sample,
+            extra_info_size,
+            extended_info,
+        })
+    }
+
+    fn read_fact_chunk(
+        &mut self,
+        header: ChunkHeader,
+        endianness: Endianness,
+    ) -> Result<FactChunk, HeaderError> {
+        let ChunkHeader { id, data_size, .. } = header;
+
+        if data_size < 4 {
+            return Err(HeaderError::InvalidFactChunk);
+        }
+
+        let sample_length = self.read_u32(endianness)?;
+
+        let mut remaining_data = vec![0; (data_size - 4) as usize];
+        self.read_exact(&mut remaining_data)?;
+        self.skip((data_size % 2) as u64)?;
+
+        Ok(FactChunk {
+            id,
+            data_size,
+            sample_length,
+            remaining_data,
+        })
+    }
+
+    fn read_fourcc(&mut self) -> Result<FourCC, HeaderError> {
+        let mut buf = [0; 4];
+
+        self.read_exact(&mut buf)?;
+
+        Ok(FourCC::from(buf))
+    }
+
+    fn read_u16(&mut self, endianness: Endianness) -> Result<u16, HeaderError> {
+        let mut buf = [0; 2];
+
+        self.read_exact(&mut buf)?;
+
+        Ok(match endianness {
+            Endianness::Little => LittleEndian::read_u16(&buf),
+            Endianness::Big => BigEndian::read_u16(&buf),
+        })
+    }
+
+    fn read_u32(&mut self, endianness: Endianness) -> Result<u32, HeaderError> {
+        let mut buf = [0; 4];
+
+        self.read_exact(&mut buf)?;
+
+        Ok(match endianness {
+            Endianness::Little => LittleEndian::read_u32(&buf),
+            Endianness::Big => BigEndian::read_u32(&buf),
+        })
+    }
+
+    fn read_u64(&mut self, endianness: Endianness) -> Result<u64, HeaderError> {
+        let mut buf = [0; 8];
+
+        self.read_exact(&mut buf)?;
+
+        Ok(match endianness {
+            Endianness::Little => LittleEndian::read_u64(&buf),
+            Endianness::Big => BigEndian::read_u64(&buf),
+        })
+    }
+
+    /// Reads a GUID stored as its fields in the byte order of the file,
+    /// returning it in the little endian layout of riff files so that the
+    /// same GUID compares equal in either byte order.
+    fn read_guid(&mut self, endianness: Endianness) -> Result<u128, HeaderError> {
+        let data1 = self.read_u32(endianness)?;
+        let data2 = self.read_u16(endianness)?;
+        let data3 = self.read_u16(endianness)?;
+
+        let mut buf = [0; 16];
+        self.read_exact(&mut buf[8..])?;
+
+        LittleEndian::write_u32(&mut buf[..4], data1);
+        LittleEndian::write_u16(&mut buf[4..6], data2);
+        LittleEndian::write_u16(&mut buf[6..8], data3);
+
+        Ok(LittleEndian::read_u128(&buf))
+    }
+}
+
+impl<S: Source> SourceExt for S {}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::fixture::WavBuilder;
+
+    #[test]
+    fn slice_source_parses_fixture_bytes() {
+        let bytes = WavBuilder::new()
+            .fmt(2, 44100, 16)
+            .chunk(b"LIST", b"INFO")
+            .data(&[0; 8])
+            .build();
+
+        let mut source = SliceSource::new(&bytes);
+        let header = read_header(&mut source).unwrap();
+
+        assert_eq!(header.fmt_chunk, FmtChunk::pcm(2, 44100, 16));
+        assert_eq!(header.data_chunk.data_size, 8);
+        assert_eq!(header.data_offset, 56);
+        assert_eq!(source.position(), 56);
+    }
+
+    #[test]
+    fn read_source_matches_blocking_reader() {
+        let bytes = WavBuilder::new()
+            .fmt(1, 8000, 8)
+            .chunk(b"fact", &[3, 0, 0, 0])
+            .data(&[1, 2, 3])
+            .build();
+
+        let header = read_header(&mut ReadSource::new(&bytes[..])).unwrap();
+        let reader = crate::RiffWaveReader::from_bytes(bytes.clone()).unwrap();
+
+        assert_eq!(header.riff_chunk, reader.riff_chunk);
+        assert_eq!(header.fmt_chunk, reader.fmt_chunk);
+        assert_eq!(header.fact_chunk, reader.fact_chunk);
+        assert_eq!(header.data_chunk, reader.data_chunk);
+        assert_eq!(header.data_offset, reader.data_offset());
+    }
+
+    #[test]
+    fn truncated_source_is_an_error() {
+        let bytes = WavBuilder::new().fmt(1, 8000, 8).build();
+
+        let result = read_header(&mut SliceSource::new(&bytes[..30]));
+
+        assert_eq!(result.unwrap_err(), HeaderError::UnexpectedEof);
+    }
+
+    #[test]
+    fn read_source_skips_chunks_without_seeking() {
+        struct ReadOnly<'a>(&'a [u8]);
+
+        impl Read for ReadOnly<'_> {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                self.0.read(buf)
+            }
+        }
+
+        let bytes = WavBuilder::new()
+            .fmt(2, 44100, 16)
+            .chunk(b"junk", &[0; 9000])
+            .data(&[0; 8])
+            .build();
+
+        let mut source = ReadSource::new(ReadOnly(&bytes));
+        let header = read_header(&mut source).unwrap();
+
+        assert_eq!(header.fmt_chunk, FmtChunk::pcm(2, 44100, 16));
+        assert_eq!(header.data_offset, 36 + 9008 + 8);
+        assert_eq!(source.position(), header.data_offset);
+        assert_eq!(source.into_inner().0, [0; 8]);
+    }
+}
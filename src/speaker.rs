@@ -0,0 +1,50 @@
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SpeakerPosition {
+    FrontLeft,
+    FrontRight,
+    FrontCenter,
+    LowFrequency,
+    BackLeft,
+    BackRight,
+    FrontLeftOfCenter,
+    FrontRightOfCenter,
+    BackCenter,
+    SideLeft,
+    SideRight,
+    TopCenter,
+    TopFrontLeft,
+    TopFrontCenter,
+    TopFrontRight,
+    TopBackLeft,
+    TopBackCenter,
+    TopBackRight,
+}
+
+impl SpeakerPosition {
+    /// All positions, ordered by their bit in the channel mask
+    pub const ALL: [SpeakerPosition; 18] = [
+        SpeakerPosition::FrontLeft,
+        SpeakerPosition::FrontRight,
+        SpeakerPosition::FrontCenter,
+        SpeakerPosition::LowFrequency,
+        SpeakerPosition::BackLeft,
+        SpeakerPosition::BackRight,
+        SpeakerPosition::FrontLeftOfCenter,
+        SpeakerPosition::FrontRightOfCenter,
+        SpeakerPosition::BackCenter,
+        SpeakerPosition::SideLeft,
+        SpeakerPosition::SideRight,
+        SpeakerPosition::TopCenter,
+        SpeakerPosition::TopFrontLeft,
+        SpeakerPosition::TopFrontCenter,
+        SpeakerPosition::TopFrontRight,
+        SpeakerPosition::TopBackLeft,
+        SpeakerPosition::TopBackCenter,
+        SpeakerPosition::TopBackRight,
+    ];
+
+    pub fn mask(self) -> u32 {
+        1 << self as u32
+    }
+}
@@ -0,0 +1,115 @@
+use std::io::Cursor;
+
+use crate::{Endianness, Error, FourCC, ReadExt};
+
+/// A segment of a `wavl` list, which holds the waveform as alternating
+/// `data` and `slnt` chunks in place of a single data chunk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WaveSegment {
+    /// Sample data starting at `offset` in the stream.
+    Data { offset: u64, data_size: u32 },
+    /// A run of silent frames.
+    Silence { num_frames: u32 },
+}
+
+impl WaveSegment {
+    pub fn num_frames(&self, block_align: u16) -> u64 {
+        match self {
+            WaveSegment::Data { data_size, .. } => (*data_size as u64)
+                .checked_div(block_align as u64)
+                .unwrap_or(0),
+            WaveSegment::Silence { num_frames } => *num_frames as u64,
+        }
+    }
+}
+
+/// Reads the segments of a `wavl` list whose header starts at `offset`,
+/// returning `None` for other list types.
+pub(crate) fn read_wave_segments(
+    list_data: &[u8],
+    offset: u64,
+    endianness: Endianness,
+) -> Result<Option<Vec<WaveSegment>>, Error> {
+    let mut reader = Cursor::new(list_data);
+
+    match reader.read_fourcc_or_eof()? {
+        Some(FourCC::Other(list_type)) if list_type == "wavl" => {}
+        _ => return Ok(None),
+    }
+
+    let mut segments = vec![];
+
+    while let Some(id) = reader.read_fourcc_or_eof()? {
+        let data_size = reader.read_u32(endianness)?;
+        if data_size as u64 > list_data.len() as u64 - reader.position() {
+            return Err(Error::InvalidListChunk);
+        }
+
+        let end = reader.position() + data_size as u64;
+
+        match id {
+            FourCC::Data => segments.push(WaveSegment::Data {
+                offset: offset + 8 + reader.position(),
+                data_size,
+            }),
+            FourCC::Other(id) if id == "slnt" && data_size >= 4 => {
+                segments.push(WaveSegment::Silence {
+                    num_frames: reader.read_u32(endianness)?,
+                })
+            }
+            _ => {}
+        }
+
+        reader.set_position(end + (data_size % 2) as u64);
+    }
+
+    Ok(Some(segments))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture::{list_data, pcm16, u32_bytes, WavBuilder};
+
+    #[test]
+    fn wavl_list_holds_data_and_silence_segments() {
+        let endianness = Endianness::Little;
+        let list = list_data(
+            endianness,
+            b"wavl",
+            &[
+                (b"data", &pcm16(endianness, &[1, 2, 3])),
+                (b"slnt", &u32_bytes(endianness, 5)),
+            ],
+        );
+
+        let reader = WavBuilder::new()
+            .fmt(1, 8000, 16)
+            .chunk(b"LIST", &list)
+            .reader();
+
+        assert_eq!(
+            reader.wave_segments,
+            Some(vec![
+                WaveSegment::Data {
+                    offset: 56,
+                    data_size: 6
+                },
+                WaveSegment::Silence { num_frames: 5 },
+            ])
+        );
+        assert_eq!(reader.wave_segment_frames(), Some(8));
+        assert_eq!(reader.data_chunk.data_size, 0);
+    }
+
+    #[test]
+    fn other_list_types_have_no_segments() {
+        let list = list_data(Endianness::Little, b"INFO", &[(b"INAM", b"name")]);
+
+        assert_eq!(
+            read_wave_segments(&list, 0, Endianness::Little).unwrap(),
+            None
+        );
+    }
+}
@@ -0,0 +1,908 @@
+use byteorder::{BigEndian, ByteOrder, LittleEndian};
+
+use std::convert::TryFrom;
+use std::io;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
+
+use crate::{
+    check_chunk_size, crc::crc32, info, ChunkInfo, Endianness, Error, FmtChunk, Format, FourCC,
+    ParseKind, ReadExt, RiffWaveReader,
+};
+
+#[derive(Debug)]
+pub struct RiffWaveWriter<W: Write + Seek> {
+    writer: W,
+    pub fmt_chunk: FmtChunk,
+    riff_offset: u64,
+    data_offset: u64,
+    data_size: u32,
+    checksum: u32,
+    /// Chunks following the data of a reopened file, written back after it.
+    trailing: Vec<u8>,
+}
+
+impl<W: Write + Seek> RiffWaveWriter<W> {
+    pub fn new(mut writer: W, fmt_chunk: FmtChunk) -> Result<RiffWaveWriter<W>, Error> {
+        let riff_offset = writer.stream_position()?;
+
+        writer.write_riff_chunk(0)?;
+        writer.write_fmt_chunk(&fmt_chunk)?;
+        writer.write_data_chunk(0)?;
+
+        let data_offset = writer.stream_position()?;
+
+        Ok(RiffWaveWriter {
+            writer,
+            fmt_chunk,
+            riff_offset,
+            data_offset,
+            data_size: 0,
+            checksum: 0,
+            trailing: vec![],
+        })
+    }
+
+    pub fn write_data(&mut self, data: &[u8]) -> Result<(), Error> {
+        let data_size = u32::try_from(data.len())
+            .ok()
+            .and_then(|size| self.data_size.checked_add(size))
+            .ok_or(Error::DataTooLarge)?;
+
+        self.writer.write_all(data)?;
+        self.data_size = data_size;
+        self.checksum = crc32(self.checksum, data);
+
+        Ok(())
+    }
+
+    /// Returns the number of data bytes written so far, excluding the pad
+    /// byte.
+    pub fn bytes_written(&self) -> u64 {
+        self.data_size as u64
+    }
+
+    /// Returns the CRC-32 of the data bytes written so far, which can be
+    /// compared against the data read back after finalizing.
+    pub fn checksum(&self) -> u32 {
+        self.checksum
+    }
+
+    pub fn write_samples_i16(&mut self, samples: &[i16]) -> Result<(), Error> {
+        if self.fmt_chunk.bits_per_raw_sample != 16 {
+            return Err(Error::UnsupportedBitDepth(
+                self.fmt_chunk.bits_per_raw_sample,
+            ));
+        }
+
+        let mut data = vec![0; samples.len() * 2];
+        LittleEndian::write_i16_into(samples, &mut data);
+
+        self.write_data(&data)
+    }
+
+    /// Writes 24-bit samples, taking the low 24 bits of each value.
+    pub fn write_samples_i24(&mut self, samples: &[i32]) -> Result<(), Error> {
+        if self.fmt_chunk.bits_per_raw_sample != 24 {
+            return Err(Error::UnsupportedBitDepth(
+                self.fmt_chunk.bits_per_raw_sample,
+            ));
+        }
+
+        let mut data = vec![0; samples.len() * 3];
+        for (sample, buf) in samples.iter().zip(data.chunks_exact_mut(3)) {
+            LittleEndian::write_i24(buf, *sample);
+        }
+
+        self.write_data(&data)
+    }
+
+    pub fn finalize(mut self) -> Result<W, Error> {
+        let pad_byte = self.data_size % 2;
+        if pad_byte == 1 {
+            self.writer.write_all(&[0])?;
+        }
+
+        self.writer.write_all(&self.trailing)?;
+
+        let end = self.writer.stream_position()?;
+
+        let file_size = (self.data_offset - self.riff_offset - 8)
+            .checked_add(self.data_size as u64 + pad_byte as u64)
+            .and_then(|size| size.checked_add(self.trailing.len() as u64))
+            .and_then(|size| u32::try_from(size).ok())
+            .ok_or(Error::DataTooLarge)?;
+
+        self.writer.seek(SeekFrom::Start(self.riff_offset + 4))?;
+        self.writer.write_u32(file_size)?;
+
+        self.writer.seek(SeekFrom::Start(self.data_offset - 4))?;
+        self.writer.write_u32(self.data_size)?;
+
+        self.writer.seek(SeekFrom::Start(end))?;
+        self.writer.flush()?;
+
+        Ok(self.writer)
+    }
+}
+
+impl<W: Read + Write + Seek> RiffWaveWriter<W> {
+    /// Reopens a finalized file to append more data. The chunks following the
+    /// data chunk are held in memory and written back after the enlarged data
+    /// by [`finalize`](Self::finalize). Only plain riff files can be reopened.
+    ///
+    /// The existing data is read once, so that [`bytes_written`] and
+    /// [`checksum`] cover the whole data chunk.
+    ///
+    /// [`bytes_written`]: Self::bytes_written
+    /// [`checksum`]: Self::checksum
+    pub fn reopen_for_append(mut writer: W) -> Result<RiffWaveWriter<W>, Error> {
+        let riff_offset = writer.stream_position()?;
+
+        let mut reader = RiffWaveReader::new(&mut writer)?;
+
+        if reader.riff_chunk.id != FourCC::Riff {
+            return Err(Error::parse(riff_offset, ParseKind::NotRiff));
+        }
+
+        if reader.is_truncated() {
+            return Err(Error::TruncatedData {
+                declared: reader.data_chunk.data_size,
+                available: reader.available_data_size(),
+            });
+        }
+
+        let data_size =
+            u32::try_from(reader.data_chunk.data_size).map_err(|_| Error::DataTooLarge)?;
+
+        let data_offset = reader.data_offset();
+        let data_end = reader.data_end();
+        let riff_end = riff_offset + 8 + reader.riff_chunk.file_size as u64;
+        let fmt_chunk = reader.fmt_chunk.clone();
+
+        let checksum = reader.data_crc32()?;
+
+        drop(reader);
+
+        writer.seek(SeekFrom::Start(data_end))?;
+        let mut trailing = vec![];
+        (&mut writer)
+            .take(riff_end.saturating_sub(data_end))
+            .read_to_end(&mut trailing)?;
+
+        // The pad byte is rewritten by finalize if still needed
+        writer.seek(SeekFrom::Start(data_offset + data_size as u64))?;
+
+        Ok(RiffWaveWriter {
+            writer,
+            fmt_chunk,
+            riff_offset,
+            data_offset,
+            data_size,
+            checksum,
+            trailing,
+        })
+    }
+}
+
+/// Wraps raw interleaved PCM bytes in a complete wave file with a plain PCM
+/// fmt chunk derived from the channel count, sample rate and bit depth.
+pub fn write_pcm<W: Write + Seek>(
+    out: W,
+    pcm: &[u8],
+    channels: u16,
+    sample_rate: u32,
+    bits: u16,
+) -> Result<(), Error> {
+    let mut writer = RiffWaveWriter::new(out, FmtChunk::pcm(channels, sample_rate, bits))?;
+
+    writer.write_data(pcm)?;
+    writer.finalize()?;
+
+    Ok(())
+}
+
+/// Writes a single wave file to `out` containing the data of `a` followed by
+/// the data of `b`.
+///
+/// Both readers must have compatible fmt chunks, and their whole data block is
+/// copied wherever they are positioned. The fmt chunk of `a` is used for the
+/// output file. The reader positions are restored afterwards.
+/// The output is always little endian, so the samples of RIFX inputs are
+/// byte swapped, which is only possible for PCM, float and G.711 data.
+pub fn concat<A, B, W>(
+    a: &mut RiffWaveReader<A>,
+    b: &mut RiffWaveReader<B>,
+    mut out: W,
+) -> Result<(), Error>
+where
+    A: Read + Seek,
+    B: Read + Seek,
+    W: Write,
+{
+    if !a.fmt_chunk.is_compatible_with(&b.fmt_chunk) {
+        return Err(Error::IncompatibleFormat);
+    }
+
+    let a_swap = swap_size(a)?;
+    let b_swap = swap_size(b)?;
+
+    let data_size = a
+        .data_chunk
+        .data_size
+        .checked_add(b.data_chunk.data_size)
+        .and_then(|size| u32::try_from(size).ok())
+        .ok_or(Error::DataTooLarge)?;
+    let pad_byte = data_size % 2;
+
+    let file_size = (4 + 8 + fmt_chunk_size(&a.fmt_chunk) + 8)
+        .checked_add(data_size)
+        .and_then(|size| size.checked_add(pad_byte))
+        .ok_or(Error::DataTooLarge)?;
+
+    out.write_riff_chunk(file_size)?;
+    out.write_fmt_chunk(&a.fmt_chunk)?;
+    out.write_data_chunk(data_size)?;
+
+    let a_position = a.reader.stream_position()?;
+    let b_position = b.reader.stream_position()?;
+
+    a.rewind_data()?;
+    copy_data(&mut a.reader, &mut out, a.data_chunk.data_size, a_swap)?;
+    b.rewind_data()?;
+    copy_data(&mut b.reader, &mut out, b.data_chunk.data_size, b_swap)?;
+
+    if pad_byte == 1 {
+        out.write_all(&[0])?;
+    }
+
+    a.reader.seek(SeekFrom::Start(a_position))?;
+    b.reader.seek(SeekFrom::Start(b_position))?;
+
+    Ok(())
+}
+
+impl<T: Read + Seek> RiffWaveReader<T> {
+    /// Copies the file to `out`, leaving out every chunk matching `id` and
+    /// recomputing the riff size. The surviving chunks keep their order,
+    /// padding and id bytes. RF64 and BW64 files are written as plain riff
+    /// files, without their ds64 chunk.
+    ///
+    /// The reader position is restored afterwards.
+    pub fn write_without_chunk<W: Write>(&mut self, out: W, id: &str) -> Result<(), Error> {
+        let id = FourCC::from(id.as_bytes());
+
+        let chunks = self
+            .chunk_layout()
+            .into_iter()
+            .filter(|chunk| chunk.id != id)
+            .map(CopyChunk::Copy)
+            .collect::<Vec<_>>();
+
+        self.write_chunks(out, chunks)
+    }
+
+    /// Copies the file to `out` like [`write_without_chunk`], setting the
+    /// given `LIST`/`INFO` tags. Tags already in the file are kept unless
+    /// replaced by a tag with the same id, and all `INFO` lists are merged
+    /// into one, placed where the first one was or before the data chunk.
+    ///
+    /// [`write_without_chunk`]: RiffWaveReader::write_without_chunk
+    pub fn write_with_info<W: Write>(
+        &mut self,
+        out: W,
+        tags: &[(FourCC, String)],
+    ) -> Result<(), Error> {
+        let position = self.reader.stream_position()?;
+
+        let mut merged = vec![];
+        let mut chunks = vec![];
+        let mut info_index = None;
+
+        for chunk in self.chunk_layout() {
+            if chunk.id == FourCC::Other(String::from("LIST")) {
+                check_chunk_size(chunk.size, self.options.max_chunk_size)?;
+
+                self.reader.seek(SeekFrom::Start(chunk.offset + 8))?;
+                let data = self.reader.read_bytes(chunk.size as usize)?;
+
+                if let Some(info_tags) = info::read_info_tags(&data, self.endianness)? {
+                    merged.extend(info_tags.into_iter().map(|tag| (tag.id, tag.value)));
+                    info_index.get_or_insert(chunks.len());
+
+                    continue;
+                }
+            }
+
+            if chunk.id == FourCC::Data {
+                info_index.get_or_insert(chunks.len());
+            }
+
+            chunks.push(CopyChunk::Copy(chunk));
+        }
+
+        self.reader.seek(SeekFrom::Start(position))?;
+
+        merged.retain(|(id, _)| tags.iter().all(|(tag_id, _)| tag_id != id));
+        merged.extend(tags.iter().cloned());
+
+        let list = CopyChunk::New {
+            id: *b"LIST",
+            data: info_list_data(&merged, self.endianness),
+        };
+        chunks.insert(info_index.unwrap_or(chunks.len()), list);
+
+        self.write_chunks(out, chunks)
+    }
+
+    fn write_chunks<W: Write>(&mut self, mut out: W, chunks: Vec<CopyChunk>) -> Result<(), Error> {
+        let data_size =
+            u32::try_from(self.data_chunk.data_size).map_err(|_| Error::DataTooLarge)?;
+
+        let chunks = chunks
+            .into_iter()
+            .filter(|chunk| !matches!(chunk, CopyChunk::Copy(info) if info.id == FourCC::Ds64))
+            .map(|chunk| {
+                let size = match &chunk {
+                    CopyChunk::Copy(info) if info.id == FourCC::Data => data_size,
+                    CopyChunk::Copy(info) => info.size,
+                    CopyChunk::New { data, .. } => data.len() as u32,
+                };
+
+                (chunk, size)
+            })
+            .collect::<Vec<_>>();
+
+        let file_size = chunks
+            .iter()
+            .map(|(_, size)| 8 + *size as u64 + (*size % 2) as u64)
+            .sum::<u64>()
+            + 4;
+        let file_size = u32::try_from(file_size).map_err(|_| Error::DataTooLarge)?;
+
+        let riff_id = if self.endianness == Endianness::Big {
+            FourCC::Rifx
+        } else {
+            FourCC::Riff
+        };
+
+        out.write_chunk_header(riff_id.as_bytes(), file_size, self.endianness)?;
+        out.write_all(b"WAVE")?;
+
+        let position = self.reader.stream_position()?;
+
+        for (chunk, size) in chunks {
+            match chunk {
+                CopyChunk::Copy(info) => {
+                    self.reader.seek(SeekFrom::Start(info.offset + 8))?;
+
+                    out.write_chunk_header(info.raw_id, size, self.endianness)?;
+                    copy_exact(&mut self.reader, &mut out, size as u64)?;
+                }
+                CopyChunk::New { id, data } => {
+                    out.write_chunk_header(id, size, self.endianness)?;
+                    out.write_all(&data)?;
+                }
+            }
+
+            if size % 2 == 1 {
+                out.write_all(&[0])?;
+            }
+        }
+
+        self.reader.seek(SeekFrom::Start(position))?;
+
+        Ok(())
+    }
+}
+
+/// A chunk written by [`RiffWaveReader::write_chunks`], either copied from
+/// the source file or built in memory.
+enum CopyChunk {
+    Copy(ChunkInfo),
+    New { id: [u8; 4], data: Vec<u8> },
+}
+
+fn info_list_data(tags: &[(FourCC, String)], endianness: Endianness) -> Vec<u8> {
+    let mut data = b"INFO".to_vec();
+
+    for (id, value) in tags {
+        let size = value.len() as u32 + 1;
+
+        let mut buf = [0; 4];
+        match endianness {
+            Endianness::Little => LittleEndian::write_u32(&mut buf, size),
+            Endianness::Big => BigEndian::write_u32(&mut buf, size),
+        }
+
+        data.extend_from_slice(&id.as_bytes());
+        data.extend_from_slice(&buf);
+        data.extend_from_slice(value.as_bytes());
+        data.push(0);
+
+        if size % 2 == 1 {
+            data.push(0);
+        }
+    }
+
+    data
+}
+
+fn fmt_chunk_size(fmt_chunk: &FmtChunk) -> u32 {
+    if fmt_chunk.data_size < 18 {
+        16
+    } else {
+        18 + fmt_chunk.extra_info_size as u32
+    }
+}
+
+/// Returns the size of the samples to byte swap when copying the data of
+/// `reader` into a little endian file, or `None` if it is little endian.
+fn swap_size<T: Read + Seek>(reader: &RiffWaveReader<T>) -> Result<Option<usize>, Error> {
+    if reader.endianness == Endianness::Little {
+        return Ok(None);
+    }
+
+    match reader.fmt_chunk.effective_format() {
+        Format::UncompressedPCM
+        | Format::IeeeFloatingPoint
+        | Format::G711ALaw
+        | Format::G711ULaw => {}
+        format => return Err(Error::UnsupportedFormat(format)),
+    }
+
+    Ok(Some(reader.fmt_chunk.bytes_per_sample()? as usize))
+}
+
+/// Copies `size` data bytes, reversing the bytes of each sample if a swap
+/// size is given.
+fn copy_data<R: Read, W: Write>(
+    reader: &mut R,
+    out: &mut W,
+    size: u64,
+    swap_size: Option<usize>,
+) -> Result<(), Error> {
+    let sample_size = match swap_size {
+        Some(sample_size) => sample_size,
+        None => return copy_exact(reader, out, size),
+    };
+
+    let mut buf = vec![0; sample_size * 1024];
+    let mut remaining = size;
+
+    while remaining > 0 {
+        let len = (buf.len() as u64).min(remaining) as usize;
+        let buf = &mut buf[..len];
+
+        reader.read_exact(buf)?;
+        for sample in buf.chunks_mut(sample_size) {
+            sample.reverse();
+        }
+        out.write_all(buf)?;
+
+        remaining -= len as u64;
+    }
+
+    Ok(())
+}
+
+fn copy_exact<R: Read, W: Write>(reader: &mut R, out: &mut W, size: u64) -> Result<(), Error> {
+    let copied = io::copy(&mut reader.take(size), out)?;
+
+    if copied != size {
+        return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+    }
+
+    Ok(())
+}
+
+trait WriteExt: Write {
+    fn write_riff_chunk(&mut self, file_size: u32) -> Result<(), Error>;
+
+    fn write_fmt_chunk(&mut self, fmt_chunk: &FmtChunk) -> Result<(), Error>;
+
+    fn write_data_chunk(&mut self, data_size: u32) -> Result<(), Error>;
+
+    fn write_chunk_header(
+        &mut self,
+        id: [u8; 4],
+        data_size: u32,
+        endianness: Endianness,
+    ) -> Result<(), Error>;
+
+    fn write_u32(&mut self, n: u32) -> Result<(), Error>;
+
+    fn write_u16(&mut self, n: u16) -> Result<(), Error>;
+
+    fn write_u128(&mut self, n: u128) -> Result<(), Error>;
+}
+
+impl<T: Write> WriteExt for T {
+    fn write_riff_chunk(&mut self, file_size: u32) -> Result<(), Error> {
+        self.write_all(b"RIFF")?;
+        self.write_u32(file_size)?;
+        self.write_all(b"WAVE")?;
+
+        Ok(())
+    }
+
+    fn write_fmt_chunk(&mut self, fmt_chunk: &FmtChunk) -> Result<(), Error> {
+        let data_size = fmt_chunk_size(fmt_chunk);
+
+        self.write_all(b"fmt ")?;
+        self.write_u32(data_size)?;
+        self.write_u16(fmt_chunk.format.into())?;
+        self.write_u16(fmt_chunk.num_channels)?;
+        self.write_u32(fmt_chunk.sample_rate)?;
+        self.write_u32(fmt_chunk.byte_rate)?;
+        self.write_u16(fmt_chunk.block_align)?;
+        self.write_u16(fmt_chunk.bits_per_raw_sample)?;
+
+        if data_size >= 18 {
+            self.write_u16(fmt_chunk.extra_info_size)?;
+        }
+
+        if let Some(extended) = &fmt_chunk.extended_info {
+            self.write_u16(extended.bits_per_coded_sample)?;
+            self.write_u32(extended.channel_mask)?;
+            self.write_u128(extended.sub_format)?;
+            self.write_all(&extended.remaining_data)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_data_chunk(&mut self, data_size: u32) -> Result<(), Error> {
+        self.write_all(b"data")?;
+        self.write_u32(data_size)?;
+
+        Ok(())
+    }
+
+    fn write_chunk_header(
+        &mut self,
+        id: [u8; 4],
+        data_size: u32,
+        endianness: Endianness,
+    ) -> Result<(), Error> {
+        let mut buf = [0; 4];
+
+        match endianness {
+            Endianness::Little => LittleEndian::write_u32(&mut buf, data_size),
+            Endianness::Big => BigEndian::write_u32(&mut buf, data_size),
+        }
+
+        self.write_all(&id)?;
+        self.write_all(&buf)?;
+
+        Ok(())
+    }
+
+    fn write_u32(&mut self, n: u32) -> Result<(), Error> {
+        let mut buf = [0; 4];
+
+        LittleEndian::write_u32(&mut buf, n);
+
+        Ok(self.write_all(&buf)?)
+    }
+
+    fn write_u16(&mut self, n: u16) -> Result<(), Error> {
+        let mut buf = [0; 2];
+
+        LittleEndian::write_u16(&mut buf, n);
+
+        Ok(self.write_all(&buf)?)
+    }
+
+    fn write_u128(&mut self, n: u128) -> Result<(), Error> {
+        let mut buf = [0; 16];
+
+        LittleEndian::write_u128(&mut buf, n);
+
+        Ok(self.write_all(&buf)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    use crate::fixture::{pcm16, WavBuilder};
+
+    #[test]
+    fn concat_joins_data_and_pads_odd_total() {
+        let mut a = WavBuilder::new().fmt(1, 8000, 8).data(&[1, 2, 3]).reader();
+        let mut b = WavBuilder::new().fmt(1, 8000, 8).data(&[4, 5]).reader();
+
+        let mut out = vec![];
+        concat(&mut a, &mut b, &mut out).unwrap();
+
+        let mut reader = RiffWaveReader::from_bytes(out.clone()).unwrap();
+        assert_eq!(reader.data_chunk.data_size, 5);
+        assert_eq!(reader.data().unwrap().collect::<Vec<_>>(), [1, 2, 3, 4, 5]);
+        assert_eq!(out.len(), 44 + 5 + 1);
+        reader.verify_file_size().unwrap();
+    }
+
+    #[test]
+    fn concat_copies_whole_data_of_partly_read_inputs() {
+        let mut a = WavBuilder::new().fmt(1, 8000, 8).data(&[1, 2, 3]).reader();
+        let mut b = WavBuilder::new().fmt(1, 8000, 8).data(&[4, 5]).reader();
+        a.seek_to_frame(2).unwrap();
+        b.seek_to_frame(2).unwrap();
+
+        let mut out = vec![];
+        concat(&mut a, &mut b, &mut out).unwrap();
+
+        let mut reader = RiffWaveReader::from_bytes(out).unwrap();
+        assert_eq!(reader.data().unwrap().collect::<Vec<_>>(), [1, 2, 3, 4, 5]);
+        assert_eq!(a.data().unwrap().collect::<Vec<_>>(), [3]);
+        assert_eq!(b.data().unwrap().count(), 0);
+    }
+
+    #[test]
+    fn concat_rejects_different_formats() {
+        let mut a = WavBuilder::new().fmt(1, 8000, 8).data(&[1]).reader();
+        let mut b = WavBuilder::new().fmt(1, 16000, 8).data(&[2]).reader();
+
+        let result = concat(&mut a, &mut b, vec![]);
+        assert!(matches!(result, Err(Error::IncompatibleFormat)));
+    }
+
+    #[test]
+    fn concat_byte_swaps_rifx_samples() {
+        let mut a = WavBuilder::rifx()
+            .fmt(1, 8000, 16)
+            .data(&pcm16(Endianness::Big, &[0x1234]))
+            .reader();
+        let mut b = WavBuilder::new()
+            .fmt(1, 8000, 16)
+            .data(&pcm16(Endianness::Little, &[-2]))
+            .reader();
+
+        let mut out = vec![];
+        concat(&mut a, &mut b, &mut out).unwrap();
+
+        let mut reader = RiffWaveReader::from_bytes(out).unwrap();
+        assert_eq!(reader.endianness, Endianness::Little);
+        assert_eq!(
+            reader.samples_i16().unwrap().collect::<Vec<_>>(),
+            [0x1234, -2]
+        );
+    }
+
+    #[test]
+    fn concat_rejects_rifx_data_that_cant_be_swapped() {
+        let mut a = WavBuilder::rifx()
+            .fmt_format(2, 1, 8000, 4)
+            .data(&[1])
+            .reader();
+        let mut b = WavBuilder::rifx()
+            .fmt_format(2, 1, 8000, 4)
+            .data(&[2])
+            .reader();
+
+        let result = concat(&mut a, &mut b, vec![]);
+        assert!(matches!(
+            result,
+            Err(Error::UnsupportedFormat(Format::Other(2)))
+        ));
+    }
+
+    #[test]
+    fn written_file_reads_back() {
+        let fmt_chunk = FmtChunk::pcm(2, 44100, 16);
+        let mut writer = RiffWaveWriter::new(Cursor::new(vec![]), fmt_chunk.clone()).unwrap();
+        writer.write_samples_i16(&[1, -1, 2, -2]).unwrap();
+
+        let bytes = writer.finalize().unwrap().into_inner();
+        let mut reader = RiffWaveReader::from_bytes(bytes).unwrap();
+
+        assert_eq!(reader.fmt_chunk, fmt_chunk);
+        assert_eq!(reader.data_chunk.data_size, 8);
+        assert_eq!(
+            reader.samples_i16().unwrap().collect::<Vec<_>>(),
+            [1, -1, 2, -2]
+        );
+        reader.verify_file_size().unwrap();
+    }
+
+    #[test]
+    fn odd_sized_data_is_padded() {
+        let mut writer =
+            RiffWaveWriter::new(Cursor::new(vec![]), FmtChunk::pcm(1, 8000, 8)).unwrap();
+        writer.write_data(&[1, 2, 3]).unwrap();
+
+        let bytes = writer.finalize().unwrap().into_inner();
+        assert_eq!(bytes.len(), 44 + 3 + 1);
+        assert_eq!(bytes[47], 0);
+
+        let mut reader = RiffWaveReader::from_bytes(bytes).unwrap();
+        assert_eq!(reader.data_chunk.data_size, 3);
+        assert_eq!(reader.data_chunk.pad_byte, 1);
+        assert_eq!(reader.data().unwrap().collect::<Vec<_>>(), [1, 2, 3]);
+        reader.verify_file_size().unwrap();
+    }
+
+    #[test]
+    fn write_without_chunk_removes_matching_chunks() {
+        let mut reader = WavBuilder::new()
+            .fmt(1, 8000, 8)
+            .chunk(b"LIST", b"INFO")
+            .chunk(b"bext", &[0; 3])
+            .data(&[1, 2, 3])
+            .reader();
+
+        let mut out = vec![];
+        reader.write_without_chunk(&mut out, "LIST").unwrap();
+
+        let mut copy = RiffWaveReader::from_bytes(out).unwrap();
+        assert!(copy.get_chunk("LIST").is_none());
+        assert_eq!(copy.get_chunk("bext").unwrap().data, [0; 3]);
+        assert_eq!(copy.data().unwrap().collect::<Vec<_>>(), [1, 2, 3]);
+        copy.verify_file_size().unwrap();
+    }
+
+    #[test]
+    fn write_without_chunk_copies_one_file_of_a_concatenated_stream() {
+        let mut bytes = WavBuilder::new()
+            .fmt(1, 8000, 8)
+            .data(&[1, 2])
+            .chunk(b"LIST", b"INFO")
+            .build();
+        bytes.extend(WavBuilder::new().fmt(1, 8000, 8).data(&[3, 4]).build());
+
+        let mut reader = RiffWaveReader::from_bytes(bytes).unwrap();
+
+        let mut out = vec![];
+        reader.write_without_chunk(&mut out, "LIST").unwrap();
+
+        assert_eq!(out, WavBuilder::new().fmt(1, 8000, 8).data(&[1, 2]).build());
+    }
+
+    #[test]
+    fn data_cased_chunk_round_trips_with_its_casing() {
+        let bytes = WavBuilder::new()
+            .fmt(1, 8000, 8)
+            .chunk(b"Data", &[1, 2])
+            .build();
+        let mut reader = RiffWaveReader::from_bytes(bytes.clone()).unwrap();
+
+        let data = reader.chunk_layout().pop().unwrap();
+        assert_eq!(data.id, FourCC::Data);
+        assert_eq!(&data.raw_id, b"Data");
+
+        let mut out = vec![];
+        reader.write_without_chunk(&mut out, "LIST").unwrap();
+        assert_eq!(out, bytes);
+    }
+
+    #[test]
+    fn write_with_info_merges_tags_into_one_list() {
+        let existing = crate::fixture::list_data(
+            Endianness::Little,
+            b"INFO",
+            &[(b"INAM", b"Old\0"), (b"ICMT", b"Kept\0")],
+        );
+        let mut reader = WavBuilder::new()
+            .fmt(1, 8000, 8)
+            .chunk(b"LIST", &existing)
+            .data(&[1, 2, 3])
+            .reader();
+
+        let tags = [
+            (FourCC::from(*b"INAM"), String::from("Title")),
+            (FourCC::from(*b"IART"), String::from("Artist")),
+        ];
+        let mut out = vec![];
+        reader.write_with_info(&mut out, &tags).unwrap();
+
+        let mut copy = RiffWaveReader::from_bytes(out).unwrap();
+        let info = copy
+            .info_tags()
+            .unwrap()
+            .unwrap()
+            .into_iter()
+            .map(|tag| (tag.id.to_string(), tag.value))
+            .collect::<Vec<_>>();
+
+        assert_eq!(copy.get_chunks("LIST").count(), 1);
+        assert_eq!(
+            info,
+            [
+                (String::from("ICMT"), String::from("Kept")),
+                (String::from("INAM"), String::from("Title")),
+                (String::from("IART"), String::from("Artist")),
+            ]
+        );
+        assert_eq!(copy.data().unwrap().collect::<Vec<_>>(), [1, 2, 3]);
+        copy.verify_file_size().unwrap();
+    }
+
+    #[test]
+    fn write_with_info_limits_list_chunk_size() {
+        let bytes = WavBuilder::new()
+            .fmt(1, 8000, 8)
+            .chunk(b"LIST", &[0; 64])
+            .data(&[1])
+            .build();
+        let options = crate::ParseOptions {
+            max_chunk_size: 32,
+            skip_oversized_chunks: true,
+            ..crate::ParseOptions::default()
+        };
+        let mut reader = RiffWaveReader::with_options(Cursor::new(bytes), options).unwrap();
+
+        assert!(matches!(
+            reader.write_with_info(vec![], &[]),
+            Err(Error::ChunkTooLarge {
+                size: 64,
+                limit: 32
+            })
+        ));
+    }
+
+    #[test]
+    fn write_pcm_wraps_stereo_samples() {
+        let pcm = pcm16(Endianness::Little, &[1, -1, 2, -2, 3, -3]);
+
+        let mut out = Cursor::new(vec![]);
+        write_pcm(&mut out, &pcm, 2, 44100, 16).unwrap();
+
+        let mut reader = RiffWaveReader::from_bytes(out.into_inner()).unwrap();
+        assert_eq!(reader.fmt_chunk, FmtChunk::pcm(2, 44100, 16));
+        assert_eq!(reader.data_chunk.data_size, 12);
+        assert_eq!(reader.data().unwrap().collect::<Vec<_>>(), pcm);
+        reader.verify_file_size().unwrap();
+    }
+
+    #[test]
+    fn bytes_written_and_checksum_track_data() {
+        let mut writer =
+            RiffWaveWriter::new(Cursor::new(vec![]), FmtChunk::pcm(1, 8000, 8)).unwrap();
+        assert_eq!(writer.bytes_written(), 0);
+        assert_eq!(writer.checksum(), 0);
+
+        writer.write_data(b"1234").unwrap();
+        writer.write_data(b"56789").unwrap();
+
+        assert_eq!(writer.bytes_written(), 9);
+        assert_eq!(writer.checksum(), 0xcbf4_3926);
+
+        let bytes = writer.finalize().unwrap().into_inner();
+        let mut reader = RiffWaveReader::from_bytes(bytes).unwrap();
+        assert_eq!(reader.data_crc32().unwrap(), 0xcbf4_3926);
+    }
+
+    #[test]
+    fn reopen_for_append_grows_data_and_keeps_trailing_chunks() {
+        let bytes = WavBuilder::new()
+            .fmt(1, 8000, 16)
+            .data(&pcm16(Endianness::Little, &[1, 2]))
+            .chunk(b"LIST", b"INFO")
+            .build();
+
+        let mut writer = RiffWaveWriter::reopen_for_append(Cursor::new(bytes)).unwrap();
+        assert_eq!(writer.bytes_written(), 4);
+        writer.write_samples_i16(&[3, 4, 5]).unwrap();
+        let checksum = writer.checksum();
+
+        let bytes = writer.finalize().unwrap().into_inner();
+        let mut reader = RiffWaveReader::from_bytes(bytes).unwrap();
+
+        assert_eq!(reader.num_frames(), 5);
+        assert_eq!(
+            reader.samples_i16().unwrap().collect::<Vec<_>>(),
+            [1, 2, 3, 4, 5]
+        );
+        assert_eq!(reader.data_crc32().unwrap(), checksum);
+        assert_eq!(
+            reader.chunk_layout().last().unwrap().id,
+            FourCC::from(*b"LIST")
+        );
+        reader.verify_file_size().unwrap();
+    }
+}